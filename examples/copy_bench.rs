@@ -0,0 +1,54 @@
+use std::time::{Duration, Instant};
+
+use postgres_to_polars::{Client, ClientOptions, PgToPlResult};
+
+const USERNAME: &str = "POSTGRES_USER";
+const PASSWORD: &str = "pgpassword";
+const DATABASE: &str = "pg-database";
+
+const RUNS: u32 = 5;
+
+/// Compare `query` et `copy_out_query` sur un gros résultat
+#[tokio::main]
+async fn main() -> PgToPlResult<()> {
+    let client_options = ClientOptions::new(
+        String::from(USERNAME),
+        String::from(PASSWORD),
+        String::from(DATABASE),
+        String::from("127.0.0.1"),
+        5432,
+        true,
+    );
+    let client = Client::new(client_options).await;
+    client.connect().await?;
+
+    let rows: i64 = std::env::args()
+        .nth(1)
+        .and_then(|rows| rows.parse().ok())
+        .unwrap_or(1_000_000);
+    let query = format!(
+        "SELECT id, 'label ' || id AS label, id * 0.5 AS ratio, now() AS created_at
+        FROM generate_series(1::int8, {}::int8) AS id",
+        rows
+    );
+
+    let mut query_time = Duration::ZERO;
+    let mut copy_time = Duration::ZERO;
+    for _ in 0..RUNS {
+        let t0 = Instant::now();
+        let df = client.query(&query, vec![]).await?;
+        query_time += t0.elapsed();
+        assert_eq!(df.height() as i64, rows);
+
+        let t0 = Instant::now();
+        let df = client.copy_out_query(&query).await?;
+        copy_time += t0.elapsed();
+        assert_eq!(df.height() as i64, rows);
+    }
+
+    println!("{} rows, average over {} runs", rows, RUNS);
+    println!("query:          {:?}", query_time / RUNS);
+    println!("copy_out_query: {:?}", copy_time / RUNS);
+
+    Ok(())
+}
@@ -2,9 +2,16 @@ mod models;
 mod utils;
 
 pub use models::client::Client;
-pub use models::client_options::ClientOptions;
+pub use models::client_options::{
+    ArrayMode, BitMode, ClientOptions, EnumMode, GeometryMode, InetMode, MoneyMode, TimeTzMode,
+};
+pub use models::copy_options::CopyInOptions;
+pub use models::create_table_options::{CreateTableOptions, StructMode, TablePersistence};
 pub use models::params::BinaryParam;
 pub use models::pool::PgToPlPool;
 pub use models::pool::build_pool;
 pub use models::pool_options::PoolOptions;
+pub use models::to_param::{IntoParams, NamedParams, ToParam};
+pub use models::type_decoder::{TypeDecoder, TypeDecoderRegistry, ValueBuilder};
+pub use models::upsert::{UpsertResult, UpsertStrategy};
 pub use utils::error::{PgToPlError, PgToPlResult};
@@ -2,22 +2,33 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use super::client_options::ClientOptions;
-use super::params::BinaryParam;
+use super::client_options::{ClientOptions, DecodeOptions};
 use crate::PgToPlError;
 use crate::models::column_result::{
-    ColumnStorage, clone_storages, column_from_field, column_to_series, push_column_value,
+    ColumnDescription, ColumnStorage, clone_storages, column_from_field, columns_to_dataframe,
+    decode_pending_records, pending_record_oids, push_column_value, refresh_enum_labels,
+    result_format, stale_enum_oids,
 };
-use crate::models::params::format_params;
+use crate::models::copy_options::CopyInOptions;
+use crate::models::create_table_options::CreateTableOptions;
+use crate::models::ddl::create_table_statements;
+use crate::models::params::{
+    BinaryParam, coerce_param, coerce_params, format_params, needs_text_input, param_format,
+};
+use crate::models::to_param::{IntoParams, NamedParams, ToParam, series_to_params};
+use crate::models::type_catalog::TypeCatalog;
+use crate::models::upsert::{UpsertColumns, UpsertResult, UpsertStrategy};
+use crate::utils::copy::{CopyReader, write_copy_header, write_copy_trailer, write_copy_tuple};
 use crate::utils::error::PgToPlResult;
-use crate::utils::{error_to_string, md5_hash, print_error, statement_name};
+use crate::utils::named_params::rewrite_named_params;
+use crate::utils::{error_to_string, md5_hash, print_error, quote_identifier, statement_name};
 use bytes::{BufMut, BytesMut};
 use fallible_iterator::FallibleIterator;
 use nanoid::nanoid;
 use polars::prelude::*;
-use postgres_protocol::IsNull;
 use postgres_protocol::message::backend;
 use postgres_protocol::message::frontend;
+use postgres_protocol::{IsNull, Oid};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
 use tokio::sync::Mutex;
@@ -31,9 +42,11 @@ struct PreparedStatementInfo {
 pub struct Client {
     healthy: AtomicBool,
     options: ClientOptions,
+    decode: DecodeOptions,
     stream: Arc<Mutex<TcpStream>>,
     prepared_statements: Mutex<HashMap<String, PreparedStatementInfo>>,
     portal_count: Mutex<i32>,
+    types: Mutex<TypeCatalog>,
 }
 
 impl Client {
@@ -41,10 +54,12 @@ impl Client {
         let stream = TcpStream::connect(options.connect_url()).await.unwrap();
         Client {
             healthy: AtomicBool::new(false),
+            decode: options.decode_options(),
             options,
             stream: Arc::new(Mutex::new(stream)),
             prepared_statements: Mutex::new(HashMap::new()),
             portal_count: Mutex::new(0),
+            types: Mutex::new(TypeCatalog::default()),
         }
     }
 
@@ -72,18 +87,7 @@ impl Client {
         let mut error_to_return: Option<String> = None;
 
         loop {
-            read_buffer.reserve(8192);
-            let n = {
-                read_buffer.reserve(8192);
-                let dst = read_buffer.chunk_mut();
-                let buf: &mut [u8] =
-                    unsafe { std::slice::from_raw_parts_mut(dst.as_mut_ptr(), dst.len()) };
-                let n = stream.read(buf).await?;
-                unsafe {
-                    read_buffer.advance_mut(n);
-                }
-                n
-            };
+            let n = read_into(&mut stream, &mut read_buffer).await?;
 
             if n == 0 {
                 return Err(PgToPlError::ConnectionClosed);
@@ -136,14 +140,52 @@ impl Client {
             }
         }
 
+        let mut types = self.types.lock().await;
+        let loaded = match types.load_extension_types(&mut stream).await {
+            Ok(()) => types.load_money_scale(&mut stream).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = loaded {
+            self.mark_unhealthy();
+            return Err(e);
+        }
+
         self.mark_healthy();
 
         Ok(())
     }
 
+    /// Requête avec paramètres nommés (`:nom` ou `$nom`), réécrits en `$n` avant le Parse
+    pub async fn query_named<P>(&self, query: &str, params: &P) -> PgToPlResult<DataFrame>
+    where
+        P: NamedParams + ?Sized,
+    {
+        let (query, names) = rewrite_named_params(query)?;
+        let values = names
+            .into_iter()
+            .map(|name| {
+                params
+                    .named_param(&name)
+                    .ok_or(PgToPlError::MissingNamedParam(name))?
+                    .to_param()
+            })
+            .collect::<PgToPlResult<Vec<_>>>()?;
+        self.query(&query, values).await
+    }
+
     pub async fn query<P>(&self, query: &str, params: P) -> PgToPlResult<DataFrame>
     where
         P: IntoIterator<Item = Option<BinaryParam>>,
+    {
+        self.query_params(query, params.into_iter().collect::<Vec<_>>())
+            .await
+    }
+
+    /// Comme `query`, avec des valeurs Rust converties par `ToParam` (tuple, tranche de
+    /// `&dyn ToParam`, ...)
+    pub async fn query_params<P>(&self, query: &str, params: P) -> PgToPlResult<DataFrame>
+    where
+        P: IntoParams,
     {
         let portal_count = {
             let mut count = self.portal_count.lock().await;
@@ -154,7 +196,17 @@ impl Client {
 
         let mut buf = BytesMut::new(); // <-- au lieu de Vec<u8>
 
-        let (param_types, param_values) = format_params(params);
+        // Sans inférence, les types envoyés au Parse sont ceux des valeurs Rust ; avec,
+        // les valeurs sont converties après coup vers les types choisis par le serveur
+        let infer = self.options.infer_param_types;
+        let (client_types, client_values, client_formats, params) = if infer {
+            (Vec::new(), Vec::new(), Vec::new(), params.into_params()?)
+        } else {
+            let params = params.into_params()?;
+            let formats: Vec<i16> = params.iter().map(param_format).collect();
+            let (param_types, param_values) = format_params(params);
+            (param_types, param_values, formats, Vec::new())
+        };
 
         let name = if self.options.prepare {
             statement_name(query)
@@ -163,33 +215,77 @@ impl Client {
         };
         let mut prepared_statements = self.prepared_statements.lock().await;
 
-        let (prepare, mut columns) = match prepared_statements.get(&name) {
+        let mut stream = self.stream.lock().await;
+
+        let (mut columns, server_types) = match prepared_statements.get(&name) {
             Some(info) => {
-                if info.param_types != param_types {
+                if !infer && info.param_types != client_types {
                     return Err(PgToPlError::ParamTypeMismatch);
                 }
-                (false, info.columns.clone())
+                (clone_storages(&info.columns), info.param_types.clone())
             }
-            None => (true, Vec::new()),
-        };
+            None => {
+                // Étape 1 : Parse + Describe, pour connaître les types des colonnes avant le Bind
+                let (server_types, fields) = self
+                    .describe_statement(&mut stream, &name, query, &client_types)
+                    .await
+                    .inspect_err(|_| self.mark_unhealthy())?;
 
-        let mut stream = self.stream.lock().await;
+                let mut types = self.types.lock().await;
+                if let Err(err) = types
+                    .resolve(&mut stream, fields.iter().map(|f| f.type_oid))
+                    .await
+                {
+                    if err.breaks_connection() {
+                        self.mark_unhealthy();
+                    }
+                    return Err(err);
+                }
 
-        if prepare {
-            frontend::parse(&name, query, param_types.iter().copied(), &mut buf)?;
-            stream.write_all(&buf).await?;
+                let columns: Vec<ColumnStorage> = fields
+                    .iter()
+                    .map(|f| column_from_field(f, &self.decode, &types))
+                    .collect();
 
-            buf.clear();
-            frontend::describe(b'S', &name, &mut buf)?;
-            stream.write_all(&buf).await?;
-        }
+                // Le statement existe côté serveur dès le Parse, même si l'exécution échoue
+                if self.options.prepare {
+                    prepared_statements.insert(
+                        name.clone(),
+                        PreparedStatementInfo {
+                            param_types: if infer {
+                                server_types.clone()
+                            } else {
+                                client_types.clone()
+                            },
+                            columns: clone_storages(&columns),
+                        },
+                    );
+                }
+                (columns, server_types)
+            }
+        };
 
-        // Étape 2 : Bind avec result_format = binaire
+        let (param_formats, param_values) = if infer {
+            let params = coerce_params(params, &server_types)?;
+            let formats: Vec<i16> = params.iter().map(param_format).collect();
+            (formats, format_params(params).1)
+        } else {
+            (client_formats, client_values)
+        };
+
+        // Étape 2 : Bind avec result_format = binaire, sauf colonnes en repli texte ; les
+        // chaînes partent au format texte pour la fonction d'entrée du type cible
+        let result_formats: Vec<i16> = columns.iter().map(result_format).collect();
+        let result_formats = if result_formats.iter().all(|format| *format == 1) {
+            vec![1]
+        } else {
+            result_formats
+        };
         buf.clear();
         frontend::bind(
             &portal_name,
             &name,
-            std::iter::repeat(1).take(param_values.len()), // format binaire
+            param_formats,
             param_values.iter(),
             |val, buf| match val {
                 Some(bytes) => {
@@ -198,7 +294,7 @@ impl Client {
                 }
                 None => Ok(IsNull::Yes),
             },
-            [1],
+            result_formats,
             &mut buf,
         )
         .map_err(|_| PgToPlError::BindError)?;
@@ -209,6 +305,7 @@ impl Client {
         frontend::execute(&portal_name, 0, &mut buf)?;
         stream.write_all(&buf).await?;
 
+        buf.clear();
         frontend::close(b'P', &portal_name, &mut buf)?;
         stream.write_all(&buf).await?;
 
@@ -222,20 +319,11 @@ impl Client {
 
         let mut done = false;
 
-        let mut error_to_return: Option<String> = None;
+        // erreur serveur ou de décodage, renvoyée une fois le flux revenu à ReadyForQuery
+        let mut error_to_return: Option<PgToPlError> = None;
 
         while !done {
-            let n = {
-                read_buffer.reserve(8192);
-                let dst = read_buffer.chunk_mut();
-                let buf: &mut [u8] =
-                    unsafe { std::slice::from_raw_parts_mut(dst.as_mut_ptr(), dst.len()) };
-                let n = stream.read(buf).await?;
-                unsafe {
-                    read_buffer.advance_mut(n);
-                }
-                n
-            };
+            let n = read_into(&mut stream, &mut read_buffer).await?;
 
             if n == 0 {
                 self.mark_unhealthy();
@@ -243,48 +331,40 @@ impl Client {
             }
             while let Some(message) = backend::Message::parse(&mut read_buffer)? {
                 match message {
-                    backend::Message::RowDescription(desc) => {
-                        columns.clear();
-                        let fields = desc.fields().iterator();
-                        for field in fields {
-                            let f = field?;
-
-                            columns.push(column_from_field(&f))
-                        }
-                    }
                     backend::Message::DataRow(row) => {
                         let buf = row.buffer();
                         let mut ranges = row.ranges(); // FallibleIterator
+                        let expected = columns.len();
 
                         for (i, col) in columns.iter_mut().enumerate() {
                             let next = ranges.next()?; // Result<Option<Option<Range>>>
-                            match next {
+                            let pushed = match next {
                                 Some(Some(r)) => push_column_value(col, Some(&buf[r])),
                                 Some(None) => push_column_value(col, None),
                                 None => {
                                     prepared_statements.remove(&name);
                                     // trop peu de champs côté serveur
-                                    error_to_return = Some(format!(
-                                        "Too few fields: expected {}, got {}",
-                                        columns.len(),
-                                        i
-                                    ));
-                                    break;
+                                    Err(PgToPlError::TooFewField(i, expected))
                                 }
+                            };
+                            if let Err(err) = pushed {
+                                if error_to_return.is_none() {
+                                    error_to_return = Some(err);
+                                }
+                                break;
                             }
                         }
                         // champs en trop ?
                         if error_to_return.is_none() && ranges.next()?.is_some() {
                             prepared_statements.remove(&name);
-                            error_to_return =
-                                Some(format!("Too many fields: expected {}", columns.len()));
+                            error_to_return = Some(PgToPlError::TooManyField(columns.len()));
                         }
                     }
                     backend::Message::ReadyForQuery(_) => {
                         done = true;
-                        if let Some(err_msg) = error_to_return {
+                        if let Some(err) = error_to_return {
                             self.mark_unhealthy();
-                            return Err(PgToPlError::QueryError(err_msg));
+                            return Err(err);
                         }
 
                         self.mark_healthy();
@@ -293,6 +373,692 @@ impl Client {
                     backend::Message::ErrorResponse(error) => {
                         let error_msg = error_to_string(&error);
 
+                        if error_to_return.is_none() {
+                            error_to_return = Some(PgToPlError::QueryError(error_msg));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        self.decode_records(&mut stream, &mut columns).await?;
+        let cached = prepared_statements
+            .get_mut(&name)
+            .map(|info| info.columns.as_mut_slice());
+        self.refresh_stale_enums(&mut stream, &mut columns, cached)
+            .await?;
+        columns_to_dataframe(columns)
+    }
+
+    /// Écrit le DataFrame dans `table` via `COPY ... FROM STDIN (FORMAT binary)`.
+    /// `table` est inséré tel quel dans la requête (éventuellement qualifié par le schéma).
+    /// Renvoie le nombre de lignes copiées.
+    pub async fn copy_in(
+        &self,
+        table: &str,
+        df: &DataFrame,
+        options: CopyInOptions,
+    ) -> PgToPlResult<u64> {
+        let mut stream = self.stream.lock().await;
+        self.copy_in_locked(&mut stream, table, df, options).await
+    }
+
+    /// `copy_in` sur un flux déjà verrouillé, pour l'enchaîner dans une transaction
+    async fn copy_in_locked(
+        &self,
+        stream: &mut TcpStream,
+        table: &str,
+        df: &DataFrame,
+        options: CopyInOptions,
+    ) -> PgToPlResult<u64> {
+        let sources: Vec<String> = match options.columns {
+            Some(columns) => columns,
+            None => df
+                .get_column_names()
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+        };
+        let columns = sources
+            .iter()
+            .map(|name| df.column(name))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let column_list = sources
+            .iter()
+            .map(|name| quote_identifier(options.mapping.get(name).unwrap_or(name)))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // Types des colonnes cibles, pour encoder chaque valeur comme le serveur l'attend
+        let (_, fields) = self
+            .describe_statement(
+                stream,
+                "",
+                &format!("SELECT {} FROM {}", column_list, table),
+                &[],
+            )
+            .await?;
+        let target_types: Vec<Oid> = fields.iter().map(|f| f.type_oid).collect();
+
+        // Les chaînes vers uuid, inet, intervalles... sont converties avant le COPY
+        let mut sources = Vec::with_capacity(columns.len());
+        for (column, oid) in columns.iter().zip(&target_types) {
+            let source = match column.dtype() {
+                DataType::String | DataType::Categorical(..) | DataType::Enum(..)
+                    if needs_text_input(*oid) =>
+                {
+                    let (_, values) = series_to_params(column.as_materialized_series())?;
+                    CopySource::Encoded(self.encode_text_input(stream, *oid, values).await?)
+                }
+                _ => CopySource::Column(column),
+            };
+            sources.push(source);
+        }
+
+        let mut buf = BytesMut::new();
+        frontend::query(
+            &format!(
+                "COPY {} ({}) FROM STDIN (FORMAT binary)",
+                table, column_list
+            ),
+            &mut buf,
+        )?;
+        stream.write_all(&buf).await?;
+
+        let mut read_buffer = BytesMut::with_capacity(8192);
+        let mut error_to_return: Option<String> = None;
+        let mut copy_started = false;
+        while !copy_started {
+            let n = read_into(stream, &mut read_buffer).await?;
+            if n == 0 {
+                self.mark_unhealthy();
+                return Err(PgToPlError::ConnectionClosed);
+            }
+            while let Some(message) = backend::Message::parse(&mut read_buffer)? {
+                match message {
+                    backend::Message::CopyInResponse(_) => {
+                        copy_started = true;
+                        break;
+                    }
+                    backend::Message::ReadyForQuery(_) => {
+                        return Err(PgToPlError::QueryError(error_to_return.unwrap_or_default()));
+                    }
+                    backend::Message::ErrorResponse(error) => {
+                        let error_msg = error_to_string(&error);
+
+                        if error_to_return.is_none() {
+                            error_to_return = Some(error_msg);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Données envoyées par paquets de lignes, un message CopyData par paquet
+        let mut data = BytesMut::new();
+        write_copy_header(&mut data);
+        let mut encode_error = None;
+        let mut offset = 0;
+        loop {
+            let length = COPY_CHUNK_ROWS.min(df.height() - offset);
+            if let Err(err) = encode_copy_rows(&sources, &target_types, offset, length, &mut data) {
+                encode_error = Some(err);
+                break;
+            }
+            offset += length;
+            let last = offset >= df.height();
+            if last {
+                write_copy_trailer(&mut data);
+            }
+
+            buf.clear();
+            frontend::CopyData::new(&data[..])?.write(&mut buf);
+            stream.write_all(&buf).await?;
+            data.clear();
+            if last {
+                break;
+            }
+        }
+
+        buf.clear();
+        match &encode_error {
+            Some(err) => frontend::copy_fail(&err.to_string(), &mut buf)?,
+            None => frontend::copy_done(&mut buf),
+        }
+        stream.write_all(&buf).await?;
+
+        let mut row_count = 0;
+        loop {
+            let n = read_into(stream, &mut read_buffer).await?;
+            if n == 0 {
+                self.mark_unhealthy();
+                return Err(PgToPlError::ConnectionClosed);
+            }
+            while let Some(message) = backend::Message::parse(&mut read_buffer)? {
+                match message {
+                    backend::Message::CommandComplete(body) => {
+                        row_count = command_row_count(body.tag()?);
+                    }
+                    backend::Message::ReadyForQuery(_) => {
+                        if let Some(err) = encode_error {
+                            return Err(err);
+                        }
+                        if let Some(err_msg) = error_to_return {
+                            return Err(PgToPlError::QueryError(err_msg));
+                        }
+                        self.mark_healthy();
+                        return Ok(row_count);
+                    }
+                    backend::Message::ErrorResponse(error) => {
+                        let error_msg = error_to_string(&error);
+
+                        if error_to_return.is_none() {
+                            error_to_return = Some(error_msg);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Exécute la requête via `COPY (...) TO STDOUT (FORMAT binary)`, sans message DataRow
+    /// par ligne. Toutes les colonnes sont lues en binaire : le repli texte ne s'applique
+    /// pas et les types inconnus sont renvoyés en octets bruts.
+    ///
+    /// Pas plus rapide que `query` dans les mesures locales (exemple `copy_bench`) : à réserver
+    /// aux gros exports où le coût d'un message par ligne compte, après mesure.
+    pub async fn copy_out_query(&self, query: &str) -> PgToPlResult<DataFrame> {
+        let query = query.trim().trim_end_matches(';');
+        let options = DecodeOptions {
+            text_fallback: false,
+            ..self.decode.clone()
+        };
+
+        let mut stream = self.stream.lock().await;
+
+        // Le schéma vient du Describe de la requête, COPY ne le transmet pas
+        let (_, fields) = self.describe_statement(&mut stream, "", query, &[]).await?;
+        let mut columns: Vec<ColumnStorage> = {
+            let mut types = self.types.lock().await;
+            if let Err(err) = types
+                .resolve(&mut stream, fields.iter().map(|f| f.type_oid))
+                .await
+            {
+                if err.breaks_connection() {
+                    self.mark_unhealthy();
+                }
+                return Err(err);
+            }
+            fields
+                .iter()
+                .map(|f| column_from_field(f, &options, &types))
+                .collect()
+        };
+
+        let mut buf = BytesMut::new();
+        frontend::query(
+            &format!("COPY ({}) TO STDOUT (FORMAT binary)", query),
+            &mut buf,
+        )?;
+        stream.write_all(&buf).await?;
+
+        let mut read_buffer = BytesMut::with_capacity(8192);
+        let mut reader = CopyReader::default();
+        // erreur serveur ou de décodage, renvoyée une fois le flux revenu à ReadyForQuery
+        let mut error_to_return: Option<PgToPlError> = None;
+
+        loop {
+            let n = read_into(&mut stream, &mut read_buffer).await?;
+            if n == 0 {
+                self.mark_unhealthy();
+                return Err(PgToPlError::ConnectionClosed);
+            }
+
+            while let Some(message) = backend::Message::parse(&mut read_buffer)? {
+                match message {
+                    backend::Message::CopyData(body) => {
+                        if error_to_return.is_some() {
+                            continue;
+                        }
+                        let decoded = reader.read_rows(body.data(), |values| {
+                            if values.len() < columns.len() {
+                                return Err(PgToPlError::TooFewField(values.len(), columns.len()));
+                            }
+                            if values.len() > columns.len() {
+                                return Err(PgToPlError::TooManyField(columns.len()));
+                            }
+                            for (col, value) in columns.iter_mut().zip(values) {
+                                push_column_value(col, *value)?;
+                            }
+                            Ok(())
+                        });
+                        if let Err(err) = decoded {
+                            error_to_return = Some(err);
+                        }
+                    }
+                    backend::Message::CopyDone
+                        if error_to_return.is_none() && !reader.is_finished() =>
+                    {
+                        error_to_return = Some(PgToPlError::InvalidCopyData("missing trailer"));
+                    }
+                    backend::Message::ReadyForQuery(_) => {
+                        // le flux est resynchronisé : la connexion reste utilisable
+                        self.mark_healthy();
+                        if let Some(err) = error_to_return {
+                            return Err(err);
+                        }
+
+                        self.decode_records(&mut stream, &mut columns).await?;
+                        self.refresh_stale_enums(&mut stream, &mut columns, None)
+                            .await?;
+                        return columns_to_dataframe(columns);
+                    }
+                    backend::Message::ErrorResponse(error) => {
+                        let error_msg = error_to_string(&error);
+
+                        if error_to_return.is_none() {
+                            error_to_return = Some(PgToPlError::QueryError(error_msg));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Crée la table `name` avec une colonne par champ du schéma Polars
+    pub async fn create_table_from_schema(
+        &self,
+        name: &str,
+        schema: &Schema,
+        options: CreateTableOptions,
+    ) -> PgToPlResult<()> {
+        // Une seule requête simple : types composites et table sont créés ensemble ou pas du tout
+        let statements = create_table_statements(name, schema, &options)?;
+        self.simple_query(&statements.join(";\n")).await?;
+        Ok(())
+    }
+
+    /// Insère ou met à jour les lignes du DataFrame selon `key_columns`, dans une transaction :
+    /// copie dans une table temporaire puis `INSERT ... ON CONFLICT` ou `MERGE` vers `table`.
+    /// Dans une transaction déjà ouverte, un savepoint annule l'upsert seul en cas d'erreur.
+    pub async fn upsert_dataframe(
+        &self,
+        table: &str,
+        df: &DataFrame,
+        key_columns: &[&str],
+        strategy: UpsertStrategy,
+    ) -> PgToPlResult<UpsertResult> {
+        if key_columns.is_empty() {
+            return Err(PgToPlError::MissingKeyColumns);
+        }
+
+        // Verrou gardé du BEGIN au COMMIT : aucune autre requête ne s'intercale
+        let mut stream = self.stream.lock().await;
+        let transaction = begin_transaction(&mut stream).await?;
+        let result = self
+            .upsert_in_transaction(&mut stream, table, df, key_columns, strategy)
+            .await;
+        let result = end_transaction(&mut stream, transaction, result).await;
+        if let Err(err) = &result
+            && err.breaks_connection()
+        {
+            self.mark_unhealthy();
+        }
+        result
+    }
+
+    async fn upsert_in_transaction(
+        &self,
+        stream: &mut TcpStream,
+        table: &str,
+        df: &DataFrame,
+        key_columns: &[&str],
+        strategy: UpsertStrategy,
+    ) -> PgToPlResult<UpsertResult> {
+        let names: Vec<String> = df
+            .get_column_names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        let columns = UpsertColumns::new(&names, key_columns);
+        let staging = quote_identifier(UPSERT_STAGING_TABLE);
+
+        // Table de transit avec les types des colonnes cibles, supprimée à la fin : un COMMIT
+        // n'arrive pas avant la fin de la transaction de l'appelant
+        simple_query(
+            stream,
+            &format!(
+                "CREATE TEMPORARY TABLE {} AS SELECT {} FROM {} WITH NO DATA",
+                staging,
+                columns.columns.join(", "),
+                table
+            ),
+        )
+        .await?;
+        let copied = self
+            .copy_in_locked(stream, &staging, df, CopyInOptions::new())
+            .await?;
+
+        let count = |rows: &[Vec<Option<String>>], index: usize| -> u64 {
+            rows.first()
+                .and_then(|row| row.get(index).cloned().flatten())
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(0)
+        };
+        match strategy {
+            UpsertStrategy::OnConflict => {
+                let rows = simple_query(stream, &columns.on_conflict(table, &staging)).await?;
+                simple_query(stream, &format!("DROP TABLE {}", staging)).await?;
+                Ok(UpsertResult {
+                    inserted: count(&rows, 0),
+                    updated: count(&rows, 1),
+                })
+            }
+            UpsertStrategy::Merge => {
+                // les lignes sans correspondance sont toutes insérées par le MERGE
+                let counts = quote_identifier(UPSERT_COUNTS_TABLE);
+                let rows = simple_query(
+                    stream,
+                    &format!(
+                        "CREATE TEMPORARY TABLE {counts} (matched int8);
+                        {merge};
+                        SELECT matched FROM {counts};
+                        DROP TABLE {staging}, {counts}",
+                        merge = columns.merge(table, &staging, &counts),
+                    ),
+                )
+                .await?;
+                let matched = count(&rows, 0);
+                Ok(UpsertResult {
+                    inserted: copied - matched,
+                    updated: if columns.has_updates() { matched } else { 0 },
+                })
+            }
+        }
+    }
+
+    /// Insère le DataFrame avec `INSERT ... SELECT * FROM unnest($1::type[], ...)`, une
+    /// colonne par paramètre tableau converti vers le type de la colonne cible, par lots de
+    /// `batch_size` lignes dans une transaction. Alternative à `copy_in` quand COPY n'est pas
+    /// disponible. Dans une transaction déjà ouverte, un savepoint annule l'insertion seule en
+    /// cas d'erreur. Renvoie le nombre de lignes insérées.
+    ///
+    /// Les colonnes List et Array, celles que `create_table_from_schema` crée en tableaux
+    /// PostgreSQL, sont refusées (`UnsupportedDataType`) : unnest les aplatirait. Les
+    /// charger avec `copy_in`.
+    pub async fn insert_dataframe_unnest(
+        &self,
+        table: &str,
+        df: &DataFrame,
+        batch_size: usize,
+    ) -> PgToPlResult<u64> {
+        if df.height() == 0 {
+            return Ok(0);
+        }
+        // unnest aplatit les tableaux imbriqués
+        if let Some(column) = df
+            .get_columns()
+            .iter()
+            .find(|c| matches!(c.dtype(), DataType::List(_) | DataType::Array(_, _)))
+        {
+            return Err(PgToPlError::UnsupportedDataType(column.dtype().to_string()));
+        }
+
+        let mut stream = self.stream.lock().await;
+        let transaction = begin_transaction(&mut stream).await?;
+        let result = self
+            .insert_unnest_in_transaction(&mut stream, table, df, batch_size)
+            .await;
+        let result = end_transaction(&mut stream, transaction, result).await;
+        if let Err(err) = &result
+            && err.breaks_connection()
+        {
+            self.mark_unhealthy();
+        }
+        result
+    }
+
+    async fn insert_unnest_in_transaction(
+        &self,
+        stream: &mut TcpStream,
+        table: &str,
+        df: &DataFrame,
+        batch_size: usize,
+    ) -> PgToPlResult<u64> {
+        let columns: Vec<String> = df
+            .get_column_names()
+            .iter()
+            .map(|name| quote_identifier(name))
+            .collect();
+
+        // Types des colonnes cibles, comme pour `copy_in`
+        let (_, fields) = self
+            .describe_statement(
+                stream,
+                "",
+                &format!("SELECT {} FROM {}", columns.join(", "), table),
+                &[],
+            )
+            .await?;
+        let oids: Vec<String> = fields.iter().map(|f| f.type_oid.to_string()).collect();
+        let type_names = simple_query(
+            stream,
+            &format!(
+                "SELECT format_type(oid, NULL) FROM unnest(ARRAY[{}]::oid[]) WITH ORDINALITY AS u(oid, n) ORDER BY n",
+                oids.join(", ")
+            ),
+        )
+        .await?;
+        let arrays: Vec<String> = type_names
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| format!("${}::{}[]", i + 1, row[0].clone().unwrap_or_default()))
+            .collect();
+        let query = format!(
+            "INSERT INTO {} ({}) SELECT * FROM unnest({})",
+            table,
+            columns.join(", "),
+            arrays.join(", ")
+        );
+
+        let batch_size = batch_size.max(1);
+        let mut total = 0;
+        for offset in (0..df.height()).step_by(batch_size) {
+            let batch = df.slice(offset as i64, batch_size);
+            let params = batch
+                .get_columns()
+                .iter()
+                .map(|c| c.to_param())
+                .collect::<PgToPlResult<Vec<_>>>()?;
+            total += self.execute_unnamed(stream, &query, params).await?;
+        }
+        Ok(total)
+    }
+
+    /// Parse, Bind et Execute d'une requête sans résultat sur le statement anonyme,
+    /// renvoie le nombre de lignes de son tag CommandComplete
+    async fn execute_unnamed(
+        &self,
+        stream: &mut TcpStream,
+        query: &str,
+        params: Vec<Option<BinaryParam>>,
+    ) -> PgToPlResult<u64> {
+        let param_formats: Vec<i16> = params.iter().map(param_format).collect();
+        let (param_types, param_values) = format_params(params);
+
+        let mut buf = BytesMut::new();
+        frontend::parse("", query, param_types, &mut buf)?;
+        frontend::bind(
+            "",
+            "",
+            param_formats,
+            param_values.iter(),
+            |val, buf| match val {
+                Some(bytes) => {
+                    buf.put_slice(bytes);
+                    Ok(IsNull::No)
+                }
+                None => Ok(IsNull::Yes),
+            },
+            [1],
+            &mut buf,
+        )
+        .map_err(|_| PgToPlError::BindError)?;
+        frontend::execute("", 0, &mut buf)?;
+        frontend::sync(&mut buf);
+        stream.write_all(&buf).await?;
+
+        let mut read_buffer = BytesMut::with_capacity(8192);
+        let mut row_count = 0;
+        let mut error_to_return: Option<String> = None;
+
+        loop {
+            let n = read_into(stream, &mut read_buffer).await?;
+            if n == 0 {
+                self.mark_unhealthy();
+                return Err(PgToPlError::ConnectionClosed);
+            }
+
+            while let Some(message) = backend::Message::parse(&mut read_buffer)? {
+                match message {
+                    backend::Message::CommandComplete(body) => {
+                        row_count = command_row_count(body.tag()?);
+                    }
+                    backend::Message::ReadyForQuery(_) => {
+                        if let Some(err_msg) = error_to_return {
+                            return Err(PgToPlError::QueryError(err_msg));
+                        }
+                        return Ok(row_count);
+                    }
+                    backend::Message::ErrorResponse(error) => {
+                        let error_msg = error_to_string(&error);
+
+                        if error_to_return.is_none() {
+                            error_to_return = Some(error_msg);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Décode les records anonymes après avoir chargé les types de leurs champs
+    async fn decode_records(
+        &self,
+        stream: &mut TcpStream,
+        columns: &mut [ColumnStorage],
+    ) -> PgToPlResult<()> {
+        while let Some(oids) = pending_record_oids(columns)? {
+            let mut types = self.types.lock().await;
+            types.resolve(stream, oids).await?;
+            decode_pending_records(columns, &types)?;
+        }
+        Ok(())
+    }
+
+    /// Recharge les libellés des enums reçus hors du cache (`ALTER TYPE ... ADD VALUE`
+    /// depuis la mise en cache) et met à jour les colonnes, ainsi que celles du statement
+    async fn refresh_stale_enums(
+        &self,
+        stream: &mut TcpStream,
+        columns: &mut [ColumnStorage],
+        cached: Option<&mut [ColumnStorage]>,
+    ) -> PgToPlResult<()> {
+        let stale = stale_enum_oids(columns);
+        if stale.is_empty() {
+            return Ok(());
+        }
+        let mut types = self.types.lock().await;
+        types.reload_enum_labels(stream, &stale).await?;
+        refresh_enum_labels(columns, &types);
+        if let Some(cached) = cached {
+            refresh_enum_labels(cached, &types);
+        }
+        Ok(())
+    }
+
+    async fn simple_query(&self, query: &str) -> PgToPlResult<Vec<Vec<Option<String>>>> {
+        let mut stream = self.stream.lock().await;
+        simple_query(&mut stream, query).await
+    }
+
+    /// Encode des chaînes au format binaire de `oid` via la fonction d'entrée texte du type,
+    /// côté serveur : COPY binaire n'accepte pas de colonne au format texte
+    async fn encode_text_input(
+        &self,
+        stream: &mut TcpStream,
+        oid: Oid,
+        values: Vec<Option<BinaryParam>>,
+    ) -> PgToPlResult<Vec<Option<Vec<u8>>>> {
+        let type_name = simple_query(stream, &format!("SELECT format_type({}, NULL)", oid))
+            .await?
+            .into_iter()
+            .next()
+            .and_then(|row| row.into_iter().next().flatten())
+            .ok_or(PgToPlError::ParamCoercion(25, oid))?;
+        let query = format!(
+            "SELECT v::{} FROM unnest($1::text[]) WITH ORDINALITY AS u(v, n) ORDER BY n",
+            type_name
+        );
+        let (param_types, param_values) = format_params([Some(BinaryParam::Array {
+            element_oid: 25,
+            elements: values,
+        })]);
+
+        let mut buf = BytesMut::new();
+        frontend::parse("", &query, param_types, &mut buf)?;
+        frontend::bind(
+            "",
+            "",
+            [1],
+            param_values.iter(),
+            |val, buf| match val {
+                Some(bytes) => {
+                    buf.put_slice(bytes);
+                    Ok(IsNull::No)
+                }
+                None => Ok(IsNull::Yes),
+            },
+            [1],
+            &mut buf,
+        )
+        .map_err(|_| PgToPlError::BindError)?;
+        frontend::execute("", 0, &mut buf)?;
+        frontend::sync(&mut buf);
+        stream.write_all(&buf).await?;
+
+        let mut read_buffer = BytesMut::with_capacity(8192);
+        let mut encoded = Vec::new();
+        let mut error_to_return: Option<String> = None;
+
+        loop {
+            let n = read_into(stream, &mut read_buffer).await?;
+            if n == 0 {
+                self.mark_unhealthy();
+                return Err(PgToPlError::ConnectionClosed);
+            }
+
+            while let Some(message) = backend::Message::parse(&mut read_buffer)? {
+                match message {
+                    backend::Message::DataRow(row) => {
+                        let buf = row.buffer();
+                        let value = row.ranges().next()?.flatten();
+                        encoded.push(value.map(|r| buf[r].to_vec()));
+                    }
+                    backend::Message::ReadyForQuery(_) => {
+                        if let Some(err_msg) = error_to_return {
+                            return Err(PgToPlError::QueryError(err_msg));
+                        }
+                        return Ok(encoded);
+                    }
+                    backend::Message::ErrorResponse(error) => {
+                        let error_msg = error_to_string(&error);
+
                         if error_to_return.is_none() {
                             error_to_return = Some(error_msg);
                         }
@@ -301,20 +1067,67 @@ impl Client {
                 }
             }
         }
+    }
+
+    async fn describe_statement(
+        &self,
+        stream: &mut TcpStream,
+        name: &str,
+        query: &str,
+        param_types: &[Oid],
+    ) -> PgToPlResult<(Vec<Oid>, Vec<ColumnDescription>)> {
+        let mut buf = BytesMut::new();
+        frontend::parse(name, query, param_types.iter().copied(), &mut buf)?;
+        frontend::describe(b'S', name, &mut buf)?;
+        frontend::sync(&mut buf);
+        stream.write_all(&buf).await?;
+
+        let mut read_buffer = BytesMut::with_capacity(8192);
+        let mut server_types = Vec::new();
+        let mut fields = Vec::new();
+        let mut error_to_return: Option<String> = None;
 
-        if prepare && self.options.prepare {
-            prepared_statements.insert(
-                name.clone(),
-                PreparedStatementInfo {
-                    param_types: param_types.clone(),
-                    columns: clone_storages(&columns),
-                },
-            );
+        loop {
+            let n = read_into(stream, &mut read_buffer).await?;
+            if n == 0 {
+                self.mark_unhealthy();
+                return Err(PgToPlError::ConnectionClosed);
+            }
+
+            while let Some(message) = backend::Message::parse(&mut read_buffer)? {
+                match message {
+                    backend::Message::ParameterDescription(desc) => {
+                        server_types = desc.parameters().collect()?;
+                    }
+                    backend::Message::RowDescription(desc) => {
+                        let mut iter = desc.fields();
+                        while let Some(field) = iter.next()? {
+                            fields.push(ColumnDescription::from(&field));
+                        }
+                    }
+                    backend::Message::ReadyForQuery(_) => {
+                        // erreur du serveur (table absente...) : la connexion reste utilisable
+                        if let Some(err_msg) = error_to_return {
+                            return Err(PgToPlError::QueryError(err_msg));
+                        }
+                        return Ok((server_types, fields));
+                    }
+                    backend::Message::ErrorResponse(error) => {
+                        let error_msg = error_to_string(&error);
+
+                        if error_to_return.is_none() {
+                            error_to_return = Some(error_msg);
+                        }
+                    }
+                    _ => {}
+                }
+            }
         }
+    }
 
-        Ok(DataFrame::from_iter(
-            columns.into_iter().map(|col| column_to_series(col)),
-        ))
+    /// Décimales du type `money` selon `lc_monetary`, lu à la connexion (2 par défaut)
+    pub async fn money_scale(&self) -> usize {
+        self.types.lock().await.money_scale()
     }
 
     pub fn has_broken(&self) -> bool {
@@ -339,14 +1152,7 @@ impl Client {
         let mut error_to_return: Option<String> = None;
 
         loop {
-            read_buffer.reserve(4096);
-            let dst = read_buffer.chunk_mut();
-            let buf: &mut [u8] =
-                unsafe { std::slice::from_raw_parts_mut(dst.as_mut_ptr(), dst.len()) };
-            let n = stream.read(buf).await?;
-            unsafe {
-                read_buffer.advance_mut(n);
-            }
+            let n = read_into(&mut stream, &mut read_buffer).await?;
             if n == 0 {
                 return Err(PgToPlError::ConnectionClosed);
             }
@@ -375,3 +1181,181 @@ impl Client {
         }
     }
 }
+
+/// Table temporaire de `Client::upsert_dataframe`
+const UPSERT_STAGING_TABLE: &str = "pg_to_pl_upsert_staging";
+/// Lignes déjà présentes, comptées par le `MERGE` de `Client::upsert_dataframe`
+const UPSERT_COUNTS_TABLE: &str = "pg_to_pl_upsert_counts";
+
+/// Nombre de lignes encodées par message CopyData
+const COPY_CHUNK_ROWS: usize = 10_000;
+
+/// Colonne source de `Client::copy_in`
+enum CopySource<'a> {
+    Column(&'a Column),
+    /// Valeurs déjà encodées par le serveur (`Client::encode_text_input`)
+    Encoded(Vec<Option<Vec<u8>>>),
+}
+
+/// Encode `length` lignes à partir de `offset` au format binaire de COPY
+fn encode_copy_rows(
+    sources: &[CopySource],
+    target_types: &[Oid],
+    offset: usize,
+    length: usize,
+    data: &mut BytesMut,
+) -> PgToPlResult<()> {
+    let values = sources
+        .iter()
+        .zip(target_types)
+        .map(|(source, oid)| match source {
+            CopySource::Column(column) => {
+                let chunk = column.as_materialized_series().slice(offset as i64, length);
+                let params = series_to_params(&chunk)?
+                    .1
+                    .into_iter()
+                    .map(|param| param.map(|param| coerce_param(param, *oid)).transpose())
+                    .collect::<PgToPlResult<Vec<_>>>()?;
+                Ok(format_params(params).1)
+            }
+            CopySource::Encoded(values) => Ok(values[offset..offset + length].to_vec()),
+        })
+        .collect::<PgToPlResult<Vec<_>>>()?;
+
+    for row in 0..length {
+        write_copy_tuple(
+            values.iter().map(|column| column[row].as_deref()),
+            values.len(),
+            data,
+        );
+    }
+    Ok(())
+}
+
+/// Nombre de lignes d'un tag CommandComplete (`COPY 42`, `INSERT 0 42`...)
+fn command_row_count(tag: &str) -> u64 {
+    tag.rsplit(' ')
+        .next()
+        .and_then(|count| count.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Lit la suite du flux dans `read_buffer`, renvoie le nombre d'octets lus (0 = connexion fermée)
+async fn read_into(stream: &mut TcpStream, read_buffer: &mut BytesMut) -> PgToPlResult<usize> {
+    read_buffer.reserve(8192);
+    Ok(stream.read_buf(read_buffer).await?)
+}
+
+/// Statut de transaction du serveur (`I` hors transaction, `T` en transaction, `E` en
+/// transaction échouée), lu sur le ReadyForQuery d'un Sync seul
+async fn transaction_status(stream: &mut TcpStream) -> PgToPlResult<u8> {
+    let mut buf = BytesMut::new();
+    frontend::sync(&mut buf);
+    stream.write_all(&buf).await?;
+
+    let mut read_buffer = BytesMut::with_capacity(64);
+    loop {
+        let n = read_into(stream, &mut read_buffer).await?;
+        if n == 0 {
+            return Err(PgToPlError::ConnectionClosed);
+        }
+        while let Some(message) = backend::Message::parse(&mut read_buffer)? {
+            if let backend::Message::ReadyForQuery(body) = message {
+                return Ok(body.status());
+            }
+        }
+    }
+}
+
+/// Savepoint de `begin_transaction` dans une transaction de l'appelant
+const TRANSACTION_SAVEPOINT: &str = "pg_to_pl_savepoint";
+
+/// Transaction ouverte par `begin_transaction`
+enum Transaction {
+    /// `BEGIN` hors transaction
+    Own,
+    /// `SAVEPOINT` dans une transaction déjà ouverte, validée ou annulée par l'appelant
+    Savepoint,
+}
+
+/// `BEGIN` hors transaction, `SAVEPOINT` dans une transaction déjà ouverte
+async fn begin_transaction(stream: &mut TcpStream) -> PgToPlResult<Transaction> {
+    if transaction_status(stream).await? == b'I' {
+        simple_query(stream, "BEGIN").await?;
+        Ok(Transaction::Own)
+    } else {
+        simple_query(stream, &format!("SAVEPOINT {}", TRANSACTION_SAVEPOINT)).await?;
+        Ok(Transaction::Savepoint)
+    }
+}
+
+/// `COMMIT` ou `RELEASE` si `result` est un succès, `ROLLBACK` ou `ROLLBACK TO` sinon ;
+/// l'erreur de `result` prime
+async fn end_transaction<T>(
+    stream: &mut TcpStream,
+    transaction: Transaction,
+    result: PgToPlResult<T>,
+) -> PgToPlResult<T> {
+    let end = match (transaction, result.is_ok()) {
+        (Transaction::Own, true) => String::from("COMMIT"),
+        (Transaction::Own, false) => String::from("ROLLBACK"),
+        (Transaction::Savepoint, true) => format!("RELEASE SAVEPOINT {}", TRANSACTION_SAVEPOINT),
+        (Transaction::Savepoint, false) => format!(
+            "ROLLBACK TO SAVEPOINT {0}; RELEASE SAVEPOINT {0}",
+            TRANSACTION_SAVEPOINT
+        ),
+    };
+    let ended = simple_query(stream, &end).await;
+    let result = result?;
+    ended?;
+    Ok(result)
+}
+
+/// Exécute une requête en protocole simple (format texte) et renvoie toutes ses lignes
+pub(crate) async fn simple_query(
+    stream: &mut TcpStream,
+    query: &str,
+) -> PgToPlResult<Vec<Vec<Option<String>>>> {
+    let mut buf = BytesMut::new();
+    frontend::query(query, &mut buf)?;
+    stream.write_all(&buf).await?;
+
+    let mut read_buffer = BytesMut::with_capacity(8192);
+    let mut rows = Vec::new();
+    let mut error_to_return: Option<String> = None;
+
+    loop {
+        let n = read_into(stream, &mut read_buffer).await?;
+        if n == 0 {
+            return Err(PgToPlError::ConnectionClosed);
+        }
+
+        while let Some(message) = backend::Message::parse(&mut read_buffer)? {
+            match message {
+                backend::Message::DataRow(row) => {
+                    let buf = row.buffer();
+                    let mut values = Vec::new();
+                    let mut ranges = row.ranges();
+                    while let Some(range) = ranges.next()? {
+                        values.push(range.map(|r| String::from_utf8_lossy(&buf[r]).into_owned()));
+                    }
+                    rows.push(values);
+                }
+                backend::Message::ReadyForQuery(_) => {
+                    if let Some(err_msg) = error_to_return {
+                        return Err(PgToPlError::QueryError(err_msg));
+                    }
+                    return Ok(rows);
+                }
+                backend::Message::ErrorResponse(error) => {
+                    let error_msg = error_to_string(&error);
+
+                    if error_to_return.is_none() {
+                        error_to_return = Some(error_msg);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
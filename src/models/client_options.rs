@@ -1,3 +1,82 @@
+use postgres_protocol::Oid;
+
+use crate::models::type_decoder::{TypeDecoder, TypeDecoderRegistry};
+
+/// Représentation Polars des tableaux PostgreSQL
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArrayMode {
+    /// `List`, imbriquées pour les tableaux à plusieurs dimensions
+    #[default]
+    List,
+    /// `Array` à taille fixe si toutes les lignes ont les mêmes dimensions, `List` sinon
+    FixedWhenUniform,
+}
+
+/// Représentation Polars des types enum PostgreSQL
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EnumMode {
+    /// `Enum`, catégories dans l'ordre de tri PostgreSQL
+    #[default]
+    Enum,
+    /// `Categorical` (catégories globales)
+    Categorical,
+}
+
+/// Représentation Polars des types `inet` et `cidr`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InetMode {
+    /// Texte canonique, par exemple `10.0.0.0/8`
+    #[default]
+    String,
+    /// `Struct{family, prefix, addr}`, `addr` en binaire (4 ou 16 octets)
+    Struct,
+}
+
+/// Représentation Polars du type `money`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MoneyMode {
+    /// `Int64` en unités de 10^-scale, où scale est le nombre de décimales de `lc_monetary`
+    /// (`Client::money_scale`) : des centimes pour une devise à 2 décimales
+    #[default]
+    Minor,
+    /// `Decimal`, décimales selon `lc_monetary` lu à la connexion (un `SET lc_monetary` ultérieur
+    /// n'est pas suivi)
+    Decimal,
+}
+
+/// Représentation Polars des types `bit` et `varbit`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BitMode {
+    /// Texte de `0` et `1`, par exemple `1010`
+    #[default]
+    String,
+    /// `List<Boolean>`
+    List,
+}
+
+/// Représentation Polars du type `timetz`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeTzMode {
+    /// `Struct{time, utc_offset_seconds}`, heure locale et décalage (positif à l'est d'UTC)
+    #[default]
+    Struct,
+    /// `Time` ramené en UTC
+    Utc,
+}
+
+/// Représentation Polars des types PostGIS `geometry` et `geography`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GeometryMode {
+    /// `Binary`, WKB ISO ; le SRID (NULL si absent) dans une colonne `<nom>_srid` à la suite,
+    /// pour les colonnes de premier niveau
+    #[default]
+    Wkb,
+    /// `Struct{wkb, srid}`, WKB ISO et SRID (NULL si absent)
+    Struct,
+    /// `Binary`, EWKB reçu de PostGIS, SRID inclus
+    Ewkb,
+}
+
 #[derive(Debug, Clone)]
 pub struct ClientOptions {
     pub user: String,
@@ -6,6 +85,18 @@ pub struct ClientOptions {
     pub host: String,
     pub port: u16,
     pub prepare: bool,
+    pub array_mode: ArrayMode,
+    pub array_lower_bounds: bool, // ajoute une colonne `<nom>_lower_bounds` par colonne tableau
+    pub enum_mode: EnumMode,
+    pub inet_mode: InetMode,
+    pub trim_bpchar: bool, // retire les espaces de complétion des `char(n)`
+    pub money_mode: MoneyMode,
+    pub bit_mode: BitMode,
+    pub timetz_mode: TimeTzMode,
+    pub geometry_mode: GeometryMode,
+    pub decoders: TypeDecoderRegistry, // prioritaires sur les décodeurs natifs
+    pub text_fallback: bool, // colonnes sans décodeur binaire demandées au format texte (Utf8)
+    pub infer_param_types: bool, // types des paramètres choisis par le serveur, valeurs converties
 }
 
 impl ClientOptions {
@@ -24,10 +115,125 @@ impl ClientOptions {
             host,
             port,
             prepare,
+            array_mode: ArrayMode::default(),
+            array_lower_bounds: false,
+            enum_mode: EnumMode::default(),
+            inet_mode: InetMode::default(),
+            trim_bpchar: false,
+            money_mode: MoneyMode::default(),
+            bit_mode: BitMode::default(),
+            timetz_mode: TimeTzMode::default(),
+            geometry_mode: GeometryMode::default(),
+            decoders: TypeDecoderRegistry::default(),
+            text_fallback: false,
+            infer_param_types: false,
         }
     }
 
+    pub fn with_array_mode(mut self, array_mode: ArrayMode) -> Self {
+        self.array_mode = array_mode;
+        self
+    }
+
+    pub fn with_array_lower_bounds(mut self, array_lower_bounds: bool) -> Self {
+        self.array_lower_bounds = array_lower_bounds;
+        self
+    }
+
+    pub fn with_enum_mode(mut self, enum_mode: EnumMode) -> Self {
+        self.enum_mode = enum_mode;
+        self
+    }
+
+    pub fn with_inet_mode(mut self, inet_mode: InetMode) -> Self {
+        self.inet_mode = inet_mode;
+        self
+    }
+
+    pub fn with_trim_bpchar(mut self, trim_bpchar: bool) -> Self {
+        self.trim_bpchar = trim_bpchar;
+        self
+    }
+
+    pub fn with_money_mode(mut self, money_mode: MoneyMode) -> Self {
+        self.money_mode = money_mode;
+        self
+    }
+
+    pub fn with_bit_mode(mut self, bit_mode: BitMode) -> Self {
+        self.bit_mode = bit_mode;
+        self
+    }
+
+    pub fn with_timetz_mode(mut self, timetz_mode: TimeTzMode) -> Self {
+        self.timetz_mode = timetz_mode;
+        self
+    }
+
+    pub fn with_geometry_mode(mut self, geometry_mode: GeometryMode) -> Self {
+        self.geometry_mode = geometry_mode;
+        self
+    }
+
+    pub fn with_decoder_for_oid<D>(mut self, oid: Oid, decoder: D) -> Self
+    where
+        D: TypeDecoder + 'static,
+    {
+        self.decoders.register_oid(oid, decoder);
+        self
+    }
+
+    pub fn with_decoder_for_type<D>(mut self, type_name: &str, decoder: D) -> Self
+    where
+        D: TypeDecoder + 'static,
+    {
+        self.decoders.register_name(type_name, decoder);
+        self
+    }
+
+    pub fn with_text_fallback(mut self, text_fallback: bool) -> Self {
+        self.text_fallback = text_fallback;
+        self
+    }
+
+    pub fn with_infer_param_types(mut self, infer_param_types: bool) -> Self {
+        self.infer_param_types = infer_param_types;
+        self
+    }
+
     pub fn connect_url(&self) -> String {
         format!("{}:{}", self.host, self.port)
     }
+
+    pub(crate) fn decode_options(&self) -> DecodeOptions {
+        DecodeOptions {
+            array_mode: self.array_mode,
+            array_lower_bounds: self.array_lower_bounds,
+            enum_mode: self.enum_mode,
+            inet_mode: self.inet_mode,
+            trim_bpchar: self.trim_bpchar,
+            money_mode: self.money_mode,
+            bit_mode: self.bit_mode,
+            timetz_mode: self.timetz_mode,
+            geometry_mode: self.geometry_mode,
+            decoders: self.decoders.clone(),
+            text_fallback: self.text_fallback,
+        }
+    }
+}
+
+/// Options de `ClientOptions` utilisées pour décoder les colonnes
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DecodeOptions {
+    pub array_mode: ArrayMode,
+    pub array_lower_bounds: bool,
+    pub enum_mode: EnumMode,
+    pub inet_mode: InetMode,
+    pub trim_bpchar: bool,
+    pub money_mode: MoneyMode,
+    pub bit_mode: BitMode,
+    pub timetz_mode: TimeTzMode,
+    pub geometry_mode: GeometryMode,
+    pub decoders: TypeDecoderRegistry,
+    pub text_fallback: bool,
 }
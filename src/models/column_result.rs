@@ -1,10 +1,40 @@
+use std::{collections::HashSet, fmt, net::IpAddr, sync::Arc};
+
+use byteorder::{BigEndian, ReadBytesExt};
+use fallible_iterator::FallibleIterator;
 use polars::{
-    prelude::{DataType, ListChunked, NamedFrom, TimeUnit},
+    chunked_array::builder::get_list_builder,
+    prelude::{
+        BooleanChunked, Categories, Column, DataFrame, DataType, FrozenCategories, Int128Chunked,
+        ListChunked, NamedFrom, StructChunked, TimeUnit,
+    },
     series::{IntoSeries, Series},
 };
-use postgres_protocol::message::backend::Field;
+use postgres_protocol::{
+    Oid,
+    message::backend::Field,
+    types::{self, ArrayDimension, Range, RangeBound},
+};
+
+use crate::{
+    ArrayMode, BitMode, EnumMode, GeometryMode, InetMode, MoneyMode, PgToPlError, TimeTzMode,
+    models::{
+        client_options::DecodeOptions,
+        type_catalog::{ExtensionType, TypeCatalog, TypeKind},
+        type_decoder::{TypeDecoder, ValueBuilder},
+    },
+    utils::{
+        error::PgToPlResult,
+        numeric::{Numeric, parse_numeric},
+        range::parse_multirange,
+        record::{RecordField, parse_record},
+        tsvector::parse_tsvector,
+        vector::{parse_halfvec, parse_sparsevec, parse_vector},
+        wkb::ewkb_to_wkb,
+    },
+};
 
-use crate::utils::text_array::parse_text_array;
+const MICROS_PER_DAY: i64 = 86_400_000_000;
 
 #[derive(Debug, Clone)]
 pub struct ColumnResult<T> {
@@ -44,48 +74,803 @@ impl<T> ColumnResult<T> {
     }
 }
 
+/// Colonne de tableaux : les éléments de toutes les lignes sont stockés à plat
+/// dans `values`, décodés par le même stockage que les colonnes scalaires.
+#[derive(Debug, Clone)]
+pub struct ListColumn {
+    pub name: String,
+    pub element_oid: Oid,
+    pub values: Box<ColumnStorage>,
+    pub shapes: Vec<Option<Vec<ArrayDimension>>>, // None = tableau NULL, vide = tableau vide
+    pub ndim: Option<usize>,                      // fixé par le premier tableau non vide
+    pub mode: ArrayMode,
+    pub lower_bounds: bool,
+}
+
+impl ListColumn {
+    pub fn new(
+        name: String,
+        element_oid: Oid,
+        values: ColumnStorage,
+        options: &DecodeOptions,
+    ) -> Self {
+        ListColumn {
+            name,
+            element_oid,
+            values: Box::new(values),
+            shapes: Vec::with_capacity(1024),
+            ndim: None,
+            mode: options.array_mode,
+            lower_bounds: options.array_lower_bounds,
+        }
+    }
+
+    fn clone_empty(&self) -> Self {
+        ListColumn {
+            name: self.name.clone(),
+            element_oid: self.element_oid,
+            values: Box::new(clone_storage(&self.values)),
+            shapes: Vec::with_capacity(1024),
+            ndim: None,
+            mode: self.mode,
+            lower_bounds: self.lower_bounds,
+        }
+    }
+}
+
+/// Colonne d'un type enum : les libellés sont convertis en `Enum` (ou `Categorical`) Polars
+#[derive(Debug, Clone)]
+pub struct EnumColumn {
+    pub oid: Oid,
+    pub values: ColumnResult<String>,
+    pub categories: Vec<String>, // dans l'ordre de tri PostgreSQL
+    pub mode: EnumMode,
+}
+
+impl EnumColumn {
+    pub fn new(name: String, oid: Oid, categories: Vec<String>, options: &DecodeOptions) -> Self {
+        EnumColumn {
+            oid,
+            values: ColumnResult::new(name),
+            categories,
+            mode: options.enum_mode,
+        }
+    }
+
+    /// Libellé reçu absent des catégories en cache (ajouté par `ALTER TYPE ... ADD VALUE`)
+    fn has_unknown_label(&self) -> bool {
+        self.mode == EnumMode::Enum
+            && self
+                .values
+                .data
+                .iter()
+                .flatten()
+                .any(|label| !self.categories.contains(label))
+    }
+
+    fn clone_empty(&self) -> Self {
+        EnumColumn {
+            oid: self.oid,
+            values: self.values.clone_empty(),
+            categories: self.categories.clone(),
+            mode: self.mode,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StructField {
+    pub oid: Oid,
+    pub values: ColumnStorage, // porte le nom du champ
+}
+
+/// Colonne d'un type composite (ou d'un record) : un stockage par champ
+#[derive(Debug, Clone)]
+pub struct StructColumn {
+    pub name: String,
+    pub fields: Vec<StructField>,
+    pub validity: Vec<bool>,
+    // record anonyme : les champs (f1, f2, ...) sont créés d'après la première valeur non NULL
+    pub anonymous: Option<AnonymousRecords>,
+}
+
+/// Valeurs d'un record anonyme, gardées en binaire jusqu'à ce que les types de leurs champs
+/// soient chargés dans le catalogue (voir `pending_record_oids`)
+#[derive(Debug, Clone)]
+pub struct AnonymousRecords {
+    pub options: DecodeOptions,
+    pub values: Vec<Option<Vec<u8>>>,
+}
+
+impl StructColumn {
+    pub fn new(name: String, fields: Vec<StructField>) -> Self {
+        StructColumn {
+            name,
+            fields,
+            validity: Vec::with_capacity(1024),
+            anonymous: None,
+        }
+    }
+
+    pub fn anonymous(name: String, options: &DecodeOptions) -> Self {
+        StructColumn {
+            anonymous: Some(AnonymousRecords {
+                options: options.clone(),
+                values: Vec::new(),
+            }),
+            ..StructColumn::new(name, Vec::new())
+        }
+    }
+
+    fn clone_empty(&self) -> Self {
+        StructColumn {
+            name: self.name.clone(),
+            fields: match self.anonymous {
+                Some(_) => Vec::new(),
+                None => self
+                    .fields
+                    .iter()
+                    .map(|field| StructField {
+                        oid: field.oid,
+                        values: clone_storage(&field.values),
+                    })
+                    .collect(),
+            },
+            validity: Vec::with_capacity(1024),
+            anonymous: self.anonymous.as_ref().map(|records| AnonymousRecords {
+                options: records.options.clone(),
+                values: Vec::new(),
+            }),
+        }
+    }
+}
+
+/// Liste d'éléments par ligne (ranges d'un multirange, points d'un polygone), stockés à plat
+#[derive(Debug, Clone)]
+pub struct ItemListColumn {
+    pub name: String,
+    pub items: Box<ColumnStorage>,
+    pub lengths: Vec<Option<usize>>, // None = valeur NULL
+}
+
+impl ItemListColumn {
+    pub fn new(name: String, items: ColumnStorage) -> Self {
+        ItemListColumn {
+            name,
+            items: Box::new(items),
+            lengths: Vec::with_capacity(1024),
+        }
+    }
+
+    fn clone_empty(&self) -> Self {
+        ItemListColumn::new(self.name.clone(), clone_storage(&self.items))
+    }
+}
+
+/// Colonne `path` : struct {closed, points}
+#[derive(Debug, Clone)]
+pub struct PathColumn {
+    pub name: String,
+    pub closed: Vec<Option<bool>>,
+    pub points: ItemListColumn, // nommée `points`
+}
+
+impl PathColumn {
+    pub fn new(name: String) -> Self {
+        PathColumn {
+            name,
+            closed: Vec::with_capacity(1024),
+            points: ItemListColumn::new(String::from("points"), point_column()),
+        }
+    }
+
+    fn clone_empty(&self) -> Self {
+        PathColumn::new(self.name.clone())
+    }
+}
+
+/// Colonne `bit`/`varbit` en mode liste : bits de toutes les lignes à plat
+#[derive(Debug, Clone)]
+pub struct BitListColumn {
+    pub name: String,
+    pub bits: Vec<Option<bool>>,
+    pub lengths: Vec<Option<usize>>, // None = valeur NULL
+}
+
+impl BitListColumn {
+    pub fn new(name: String) -> Self {
+        BitListColumn {
+            name,
+            bits: Vec::with_capacity(1024),
+            lengths: Vec::with_capacity(1024),
+        }
+    }
+
+    fn clone_empty(&self) -> Self {
+        BitListColumn::new(self.name.clone())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorKind {
+    Vector,
+    HalfVec,
+    SparseVec,
+}
+
+/// Colonne pgvector : valeurs de toutes les lignes à plat, converties en float4
+#[derive(Debug, Clone)]
+pub struct VectorColumn {
+    pub name: String,
+    pub kind: VectorKind,
+    pub values: Vec<f32>,
+    pub lengths: Vec<Option<usize>>, // None = valeur NULL
+}
+
+impl VectorColumn {
+    pub fn new(name: String, kind: VectorKind) -> Self {
+        VectorColumn {
+            name,
+            kind,
+            values: Vec::with_capacity(1024),
+            lengths: Vec::with_capacity(1024),
+        }
+    }
+
+    fn clone_empty(&self) -> Self {
+        VectorColumn::new(self.name.clone(), self.kind)
+    }
+}
+
+/// Colonne `money` en `Decimal`, l'échelle dépend de `lc_monetary`
+#[derive(Debug, Clone)]
+pub struct MoneyColumn {
+    pub values: ColumnResult<i64>,
+    pub scale: usize,
+}
+
+/// Colonne PostGIS, représentée selon `GeometryMode`
+#[derive(Debug, Clone)]
+pub struct GeometryColumn {
+    pub wkb: ColumnResult<Vec<u8>>, // EWKB tel quel en `GeometryMode::Ewkb`
+    pub srids: Vec<Option<i32>>,    // ignoré en `GeometryMode::Ewkb`
+    pub mode: GeometryMode,
+}
+
+impl GeometryColumn {
+    pub fn new(name: String, mode: GeometryMode) -> Self {
+        GeometryColumn {
+            wkb: ColumnResult::new(name),
+            srids: Vec::with_capacity(1024),
+            mode,
+        }
+    }
+
+    fn clone_empty(&self) -> Self {
+        GeometryColumn::new(self.wkb.name.clone(), self.mode)
+    }
+}
+
+/// Colonne hstore : paires de toutes les lignes à plat
+#[derive(Debug, Clone)]
+pub struct HstoreColumn {
+    pub name: String,
+    pub keys: Vec<String>,
+    pub values: Vec<Option<String>>,
+    pub lengths: Vec<Option<usize>>, // None = valeur NULL
+}
+
+impl HstoreColumn {
+    pub fn new(name: String) -> Self {
+        HstoreColumn {
+            name,
+            keys: Vec::with_capacity(1024),
+            values: Vec::with_capacity(1024),
+            lengths: Vec::with_capacity(1024),
+        }
+    }
+
+    fn clone_empty(&self) -> Self {
+        HstoreColumn::new(self.name.clone())
+    }
+}
+
+/// Colonne tsvector : lexèmes de toutes les lignes à plat, et leurs positions
+#[derive(Debug, Clone)]
+pub struct TsVectorColumn {
+    pub name: String,
+    pub lexemes: Vec<String>,
+    pub positions: Vec<u16>,
+    pub weights: Vec<String>,
+    pub position_counts: Vec<Option<usize>>, // nombre de positions par lexème
+    pub lengths: Vec<Option<usize>>,         // nombre de lexèmes par ligne, None = valeur NULL
+}
+
+impl TsVectorColumn {
+    pub fn new(name: String) -> Self {
+        TsVectorColumn {
+            name,
+            lexemes: Vec::with_capacity(1024),
+            positions: Vec::with_capacity(1024),
+            weights: Vec::with_capacity(1024),
+            position_counts: Vec::with_capacity(1024),
+            lengths: Vec::with_capacity(1024),
+        }
+    }
+
+    fn clone_empty(&self) -> Self {
+        TsVectorColumn::new(self.name.clone())
+    }
+}
+
+/// Colonne décodée par un `TypeDecoder` enregistré dans les options
+#[derive(Clone)]
+pub struct CustomColumn {
+    pub name: String,
+    pub decoder: Arc<dyn TypeDecoder>,
+    pub values: ValueBuilder,
+}
+
+impl CustomColumn {
+    pub fn new(name: String, decoder: Arc<dyn TypeDecoder>) -> Self {
+        CustomColumn {
+            name,
+            decoder,
+            values: ValueBuilder::default(),
+        }
+    }
+
+    fn clone_empty(&self) -> Self {
+        CustomColumn::new(self.name.clone(), self.decoder.clone())
+    }
+}
+
+impl fmt::Debug for CustomColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomColumn")
+            .field("name", &self.name)
+            .field("dtype", &self.decoder.dtype())
+            .field("values", &self.values)
+            .finish()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ColumnStorage {
+    SmallInts(ColumnResult<i16>),
     Ints(ColumnResult<i32>),
+    BigInts(ColumnResult<i64>),
     Texts(ColumnResult<String>),
     Bools(ColumnResult<bool>),
     Dates(ColumnResult<i32>), // i32 = jours depuis 1970-01-01 (compatible Polars)
-    TextArray(ColumnResult<Vec<Option<String>>>),
     Timestamps(ColumnResult<i64>), // microsecondes depuis 1970-01-01 UTC
+    Floats(ColumnResult<f32>),
     Doubles(ColumnResult<f64>),
+    Numerics(ColumnResult<Numeric>), // échelle unifiée à la conversion en Series, NaN = NULL
+    Uuids(ColumnResult<String>),
+    Jsonb(ColumnResult<String>),
     TimestampsWtz(ColumnResult<i64>), // microsecondes depuis 2000-01-01
     Times(ColumnResult<i64>),         // microsecondes depuis minuit
-    Bytes(ColumnResult<Vec<u8>>),     // fallback
+    Arrays(ListColumn),
+    Enums(EnumColumn),
+    Structs(StructColumn),
+    Ranges(StructColumn),
+    Multiranges(ItemListColumn),
+    Geometrics(StructColumn), // champs de taille fixe : float8 ou point
+    Polygons(ItemListColumn),
+    Paths(PathColumn),
+    Inets(ColumnResult<String>),
+    Cidrs(ColumnResult<String>), // préfixe toujours affiché
+    InetStructs(StructColumn),
+    MacAddrs(ColumnResult<String>),
+    TrimmedTexts(ColumnResult<String>), // bpchar sans les espaces de complétion
+    Money(MoneyColumn),                 // converti en Decimal
+    Chars(ColumnResult<String>),        // "char" : un octet, lu en Latin-1
+    Bits(ColumnResult<String>),
+    BitLists(BitListColumn),
+    TimeTzs(StructColumn),
+    TimesUtc(ColumnResult<i64>), // timetz ramené en UTC, nanosecondes depuis minuit
+    Custom(CustomColumn),
+    TextFallbacks(ColumnResult<String>), // type sans décodeur binaire, reçu au format texte
+    Vectors(VectorColumn),
+    Geometries(GeometryColumn),
+    Hstores(HstoreColumn),
+    TsVectors(TsVectorColumn),
+    Bytes(ColumnResult<Vec<u8>>), // fallback
+}
+
+/// Description d'une colonne, issue du RowDescription
+#[derive(Debug, Clone)]
+pub struct ColumnDescription {
+    pub name: String,
+    pub type_oid: Oid,
+}
+
+impl From<&Field<'_>> for ColumnDescription {
+    fn from(field: &Field<'_>) -> Self {
+        ColumnDescription {
+            name: String::from(field.name()),
+            type_oid: field.type_oid(),
+        }
+    }
+}
+
+pub fn column_from_field(
+    field: &ColumnDescription,
+    options: &DecodeOptions,
+    types: &TypeCatalog,
+) -> ColumnStorage {
+    // le format texte ne peut être demandé que pour une colonne entière, pas pour
+    // un champ de composite ou un élément de tableau
+    if options.text_fallback
+        && column_from_oid(field.name.clone(), field.type_oid, options, types).is_none()
+    {
+        return ColumnStorage::TextFallbacks(ColumnResult::new(field.name.clone()));
+    }
+    column_or_bytes(field.name.clone(), field.type_oid, options, types)
+}
+
+/// Format de résultat à demander dans le Bind : 0 = texte, 1 = binaire
+pub fn result_format(column: &ColumnStorage) -> i16 {
+    match column {
+        ColumnStorage::TextFallbacks(_) => 0,
+        _ => 1,
+    }
+}
+
+fn column_or_bytes(
+    name: String,
+    oid: Oid,
+    options: &DecodeOptions,
+    types: &TypeCatalog,
+) -> ColumnStorage {
+    match column_from_oid(name.clone(), oid, options, types) {
+        Some(column) => column,
+        None => {
+            println!(
+                "⚠️ Unknown type column: name={}, type_oid={}, type_name={}",
+                name,
+                oid,
+                types.get(oid).map_or("?", |t| t.name.as_str())
+            );
+            ColumnStorage::Bytes(ColumnResult::new(name))
+        } // fallback: raw bytes
+    }
+}
+
+/// Stockage associé à un OID, `None` si le type n'est pas supporté
+pub fn column_from_oid(
+    name: String,
+    oid: Oid,
+    options: &DecodeOptions,
+    types: &TypeCatalog,
+) -> Option<ColumnStorage> {
+    if let Some(decoder) = options.decoders.by_oid(oid) {
+        return Some(ColumnStorage::Custom(CustomColumn::new(name, decoder)));
+    }
+    if let Some(mut column) = builtin_column(name.clone(), oid, options) {
+        if let ColumnStorage::Money(col) = &mut column {
+            col.scale = types.money_scale();
+        }
+        return Some(column);
+    }
+    if let Some(element_oid) = array_element_oid(oid) {
+        return array_column(name, element_oid, options, types);
+    }
+    if let Some(subtype) = range_subtype_oid(oid) {
+        return range_column(name, subtype, options, types);
+    }
+    if let Some(range_oid) = multirange_range_oid(oid) {
+        return multirange_column(name, range_oid, options, types);
+    }
+
+    let pg_type = types.get(oid)?;
+    if let Some(decoder) = options.decoders.by_name(&pg_type.name) {
+        return Some(ColumnStorage::Custom(CustomColumn::new(name, decoder)));
+    }
+    if let Some(extension) = types.extension(oid) {
+        return Some(extension_column(name, extension, options));
+    }
+    match pg_type.kind {
+        TypeKind::Enum => Some(ColumnStorage::Enums(EnumColumn::new(
+            name,
+            oid,
+            pg_type.enum_labels.clone(),
+            options,
+        ))),
+        TypeKind::Composite => {
+            let fields = pg_type
+                .fields
+                .iter()
+                .map(|(field_name, field_oid)| StructField {
+                    oid: *field_oid,
+                    values: column_or_bytes(field_name.clone(), *field_oid, options, types),
+                })
+                .collect();
+            Some(ColumnStorage::Structs(StructColumn::new(name, fields)))
+        }
+        TypeKind::Range => range_column(name, pg_type.subtype, options, types),
+        TypeKind::Domain => column_from_oid(name, pg_type.base, options, types),
+        // types d'extension textuels (citext, ...) : forme binaire en UTF-8
+        TypeKind::Base if pg_type.category == 'S' => {
+            Some(ColumnStorage::Texts(ColumnResult::new(name)))
+        }
+        TypeKind::Multirange => multirange_column(name, pg_type.subtype, options, types),
+        _ => array_column(name, pg_type.array_element()?, options, types),
+    }
+}
+
+/// Types d'extension décodés nativement (voir `EXTENSION_TYPES`)
+fn extension_column(
+    name: String,
+    extension: ExtensionType,
+    options: &DecodeOptions,
+) -> ColumnStorage {
+    match extension {
+        ExtensionType::Vector => {
+            ColumnStorage::Vectors(VectorColumn::new(name, VectorKind::Vector))
+        }
+        ExtensionType::HalfVec => {
+            ColumnStorage::Vectors(VectorColumn::new(name, VectorKind::HalfVec))
+        }
+        ExtensionType::SparseVec => {
+            ColumnStorage::Vectors(VectorColumn::new(name, VectorKind::SparseVec))
+        }
+        ExtensionType::Geometry => {
+            ColumnStorage::Geometries(GeometryColumn::new(name, options.geometry_mode))
+        }
+        ExtensionType::Hstore => ColumnStorage::Hstores(HstoreColumn::new(name)),
+    }
+}
+
+fn array_column(
+    name: String,
+    element_oid: Oid,
+    options: &DecodeOptions,
+    types: &TypeCatalog,
+) -> Option<ColumnStorage> {
+    let values = column_from_oid(String::new(), element_oid, options, types)?;
+    Some(ColumnStorage::Arrays(ListColumn::new(
+        name,
+        element_oid,
+        values,
+        options,
+    )))
+}
+
+/// inet/cidr : struct {family (4 ou 6), prefix, addr}
+fn inet_struct_column(name: String) -> ColumnStorage {
+    let field = |field: &str, oid: Oid| StructField {
+        oid,
+        values: builtin_column(String::from(field), oid, &DecodeOptions::default()).unwrap(),
+    };
+    let fields = vec![field("family", 21), field("prefix", 21), field("addr", 17)];
+    ColumnStorage::InetStructs(StructColumn::new(name, fields))
+}
+
+/// Types géométriques : struct de champs float8 ou point
+fn geometric_column(name: String, fields: &[(&str, Oid)]) -> ColumnStorage {
+    let fields = fields
+        .iter()
+        .map(|(field, oid)| StructField {
+            oid: *oid,
+            values: builtin_column(String::from(*field), *oid, &DecodeOptions::default()).unwrap(),
+        })
+        .collect();
+    ColumnStorage::Geometrics(StructColumn::new(name, fields))
 }
 
-pub fn column_from_field(field: &Field) -> ColumnStorage {
-    let name = String::from(field.name());
-    let oid = field.type_oid();
+fn point_column() -> ColumnStorage {
+    builtin_column(String::new(), 600, &DecodeOptions::default()).unwrap()
+}
 
+fn geometric_width(oid: Oid) -> usize {
     match oid {
-        23 => ColumnStorage::Ints(ColumnResult::new(name)), // int4
-        25 | 1043 => ColumnStorage::Texts(ColumnResult::new(name)), // text, varchar
+        600 => 16, // point
+        _ => 8,    // float8
+    }
+}
+
+/// timetz : struct {time, utc_offset_seconds}
+fn timetz_struct_column(name: String) -> ColumnStorage {
+    let field = |field: &str, oid: Oid| StructField {
+        oid,
+        values: builtin_column(String::from(field), oid, &DecodeOptions::default()).unwrap(),
+    };
+    let fields = vec![field("time", 1083), field("utc_offset_seconds", 23)];
+    ColumnStorage::TimeTzs(StructColumn::new(name, fields))
+}
+
+/// Range : struct {lower, upper, lower_inc, upper_inc, empty}, bornes du type de l'élément
+fn range_column(
+    name: String,
+    subtype: Oid,
+    options: &DecodeOptions,
+    types: &TypeCatalog,
+) -> Option<ColumnStorage> {
+    let bound = |field: &str| -> Option<StructField> {
+        Some(StructField {
+            oid: subtype,
+            values: column_from_oid(String::from(field), subtype, options, types)?,
+        })
+    };
+    let flag = |field: &str| StructField {
+        oid: 16,
+        values: ColumnStorage::Bools(ColumnResult::new(String::from(field))),
+    };
+
+    let fields = vec![
+        bound("lower")?,
+        bound("upper")?,
+        flag("lower_inc"),
+        flag("upper_inc"),
+        flag("empty"),
+    ];
+    Some(ColumnStorage::Ranges(StructColumn::new(name, fields)))
+}
+
+fn multirange_column(
+    name: String,
+    range_oid: Oid,
+    options: &DecodeOptions,
+    types: &TypeCatalog,
+) -> Option<ColumnStorage> {
+    let ranges = column_from_oid(String::new(), range_oid, options, types)?;
+    Some(ColumnStorage::Multiranges(ItemListColumn::new(
+        name, ranges,
+    )))
+}
+
+/// Vrai si le type est décodé sans consulter le catalogue
+pub fn is_builtin_oid(oid: Oid) -> bool {
+    builtin_column(String::new(), oid, &DecodeOptions::default()).is_some()
+        || array_element_oid(oid).is_some()
+        || range_subtype_oid(oid).is_some()
+        || multirange_range_oid(oid).is_some()
+}
+
+fn builtin_column(name: String, oid: Oid, options: &DecodeOptions) -> Option<ColumnStorage> {
+    let column = match oid {
+        21 => ColumnStorage::SmallInts(ColumnResult::new(name)), // int2
+        23 => ColumnStorage::Ints(ColumnResult::new(name)),      // int4
+        20 => ColumnStorage::BigInts(ColumnResult::new(name)),   // int8
+        25 | 1043 | 114 => ColumnStorage::Texts(ColumnResult::new(name)), // text, varchar, json
+        1042 if options.trim_bpchar => ColumnStorage::TrimmedTexts(ColumnResult::new(name)), // bpchar
+        1042 | 19 | 142 | 705 => ColumnStorage::Texts(ColumnResult::new(name)), // bpchar, name, xml, unknown
+        18 => ColumnStorage::Chars(ColumnResult::new(name)),                    // "char"
+        790 => match options.money_mode {
+            MoneyMode::Minor => ColumnStorage::BigInts(ColumnResult::new(name)), // money
+            MoneyMode::Decimal => ColumnStorage::Money(MoneyColumn {
+                values: ColumnResult::new(name),
+                scale: 2, // remplacée par l'échelle de la session dans `column_from_oid`
+            }),
+        },
+        1560 | 1562 => match options.bit_mode {
+            BitMode::String => ColumnStorage::Bits(ColumnResult::new(name)), // bit, varbit
+            BitMode::List => ColumnStorage::BitLists(BitListColumn::new(name)),
+        },
         16 => ColumnStorage::Bools(ColumnResult::new(name)), // bool
+        17 => ColumnStorage::Bytes(ColumnResult::new(name)), // bytea
         1082 => ColumnStorage::Dates(ColumnResult::new(name)), // date
-        1009 => ColumnStorage::TextArray(ColumnResult::new(name)), // text[]
         1184 => ColumnStorage::Timestamps(ColumnResult::new(name)), // timestamptz
+        700 => ColumnStorage::Floats(ColumnResult::new(name)), // float4
         701 => ColumnStorage::Doubles(ColumnResult::new(name)), // float8
+        1700 => ColumnStorage::Numerics(ColumnResult::new(name)), // numeric
+        2950 => ColumnStorage::Uuids(ColumnResult::new(name)), // uuid
+        3802 => ColumnStorage::Jsonb(ColumnResult::new(name)), // jsonb
         1114 => ColumnStorage::TimestampsWtz(ColumnResult::new(name)), // timestamp
         1083 => ColumnStorage::Times(ColumnResult::new(name)), // time
-        _ => {
-            println!(
-                "⚠️ Unknown type column: name={}, type_oid={}, format={}",
-                field.name(),
-                field.type_oid(),
-                field.format()
-            );
-            ColumnStorage::Bytes(ColumnResult::new(name))
-        } // fallback: raw bytes
-    }
+        3614 => ColumnStorage::TsVectors(TsVectorColumn::new(name)), // tsvector
+        600 => geometric_column(name, &[("x", 701), ("y", 701)]), // point
+        601 => geometric_column(name, &[("start", 600), ("end", 600)]), // lseg
+        603 => geometric_column(name, &[("high", 600), ("low", 600)]), // box
+        718 => geometric_column(name, &[("center", 600), ("radius", 701)]), // circle
+        628 => geometric_column(name, &[("a", 701), ("b", 701), ("c", 701)]), // line
+        604 => ColumnStorage::Polygons(ItemListColumn::new(name, point_column())), // polygon
+        602 => ColumnStorage::Paths(PathColumn::new(name)),  // path
+        1266 => match options.timetz_mode {
+            TimeTzMode::Struct => timetz_struct_column(name), // timetz
+            TimeTzMode::Utc => ColumnStorage::TimesUtc(ColumnResult::new(name)),
+        },
+        2249 => ColumnStorage::Structs(StructColumn::anonymous(name, options)), // record
+        869 | 650 if options.inet_mode == InetMode::Struct => inet_struct_column(name), // inet, cidr
+        869 => ColumnStorage::Inets(ColumnResult::new(name)),                           // inet
+        650 => ColumnStorage::Cidrs(ColumnResult::new(name)),                           // cidr
+        829 | 774 => ColumnStorage::MacAddrs(ColumnResult::new(name)), // macaddr, macaddr8
+        _ => return None,
+    };
+    Some(column)
+}
+
+/// OID de l'élément pour les types tableau connus
+pub fn array_element_oid(oid: Oid) -> Option<Oid> {
+    let element_oid = match oid {
+        1005 => 21,   // int2[]
+        1007 => 23,   // int4[]
+        1016 => 20,   // int8[]
+        1009 => 25,   // text[]
+        1015 => 1043, // varchar[]
+        199 => 114,   // json[]
+        1000 => 16,   // bool[]
+        1001 => 17,   // bytea[]
+        1182 => 1082, // date[]
+        1185 => 1184, // timestamptz[]
+        1021 => 700,  // float4[]
+        1022 => 701,  // float8[]
+        1231 => 1700, // numeric[]
+        2951 => 2950, // uuid[]
+        3807 => 3802, // jsonb[]
+        1115 => 1114, // timestamp[]
+        1183 => 1083, // time[]
+        2287 => 2249, // record[]
+        1041 => 869,  // inet[]
+        651 => 650,   // cidr[]
+        1040 => 829,  // macaddr[]
+        775 => 774,   // macaddr8[]
+        1014 => 1042, // bpchar[]
+        1002 => 18,   // "char"[]
+        1003 => 19,   // name[]
+        143 => 142,   // xml[]
+        791 => 790,   // money[]
+        1270 => 1266, // timetz[]
+        3643 => 3614, // tsvector[]
+        1017 => 600,  // point[]
+        1018 => 601,  // lseg[]
+        1020 => 603,  // box[]
+        719 => 718,   // circle[]
+        629 => 628,   // line[]
+        1027 => 604,  // polygon[]
+        1019 => 602,  // path[]
+        1561 => 1560, // bit[]
+        1563 => 1562, // varbit[]
+        3905 => 3904, // int4range[]
+        3927 => 3926, // int8range[]
+        3907 => 3906, // numrange[]
+        3909 => 3908, // tsrange[]
+        3911 => 3910, // tstzrange[]
+        3913 => 3912, // daterange[]
+        _ => return None,
+    };
+    Some(element_oid)
 }
 
-pub fn push_column_value(column: &mut ColumnStorage, value: Option<&[u8]>) {
+/// OID du type des bornes pour les ranges connus
+pub(crate) fn range_subtype_oid(oid: Oid) -> Option<Oid> {
+    let subtype = match oid {
+        3904 => 23,   // int4range
+        3926 => 20,   // int8range
+        3906 => 1700, // numrange
+        3908 => 1114, // tsrange
+        3910 => 1184, // tstzrange
+        3912 => 1082, // daterange
+        _ => return None,
+    };
+    Some(subtype)
+}
+
+/// OID du range pour les multiranges connus (PostgreSQL 14+)
+fn multirange_range_oid(oid: Oid) -> Option<Oid> {
+    let range_oid = match oid {
+        4451 => 3904, // int4multirange
+        4536 => 3926, // int8multirange
+        4532 => 3906, // nummultirange
+        4533 => 3908, // tsmultirange
+        4534 => 3910, // tstzmultirange
+        4535 => 3912, // datemultirange
+        _ => return None,
+    };
+    Some(range_oid)
+}
+
+pub fn push_column_value(column: &mut ColumnStorage, value: Option<&[u8]>) -> PgToPlResult<()> {
     match column {
+        ColumnStorage::SmallInts(col) => match value {
+            Some(bytes) if bytes.len() == 2 => {
+                let val = i16::from_be_bytes(bytes.try_into().unwrap());
+                col.push(val);
+            }
+            _ => col.push_null(),
+        },
         ColumnStorage::Ints(col) => match value {
             Some(bytes) if bytes.len() == 4 => {
                 let val = i32::from_be_bytes(bytes.try_into().unwrap());
@@ -93,9 +878,16 @@ pub fn push_column_value(column: &mut ColumnStorage, value: Option<&[u8]>) {
             }
             _ => col.push_null(),
         },
+        ColumnStorage::BigInts(col) => match value {
+            Some(bytes) if bytes.len() == 8 => {
+                let val = i64::from_be_bytes(bytes.try_into().unwrap());
+                col.push(val);
+            }
+            _ => col.push_null(),
+        },
         ColumnStorage::Texts(col) => match value {
             Some(bytes) => {
-                let val = std::str::from_utf8(bytes).unwrap().to_string();
+                let val = types::text_from_sql(bytes)?.to_string();
                 col.push(val);
             }
             _ => col.push_null(),
@@ -116,22 +908,22 @@ pub fn push_column_value(column: &mut ColumnStorage, value: Option<&[u8]>) {
             }
             _ => col.push_null(),
         },
-        ColumnStorage::TextArray(col) => match value {
-            Some(bytes) => {
-                let val = parse_text_array(bytes).unwrap();
-                col.push(val);
-            }
-            _ => col.push_null(),
-        },
         ColumnStorage::Timestamps(col) => match value {
             Some(bytes) if bytes.len() == 8 => {
                 // PostgreSQL: microseconds since 2000-01-01
                 let pg_microseconds = i64::from_be_bytes(bytes.try_into().unwrap());
-                let unix_microseconds = pg_microseconds + 946684800_000_000; // seconds between 1970-01-01 and 2000-01-01
+                let unix_microseconds = pg_microseconds + 946_684_800_000_000; // seconds between 1970-01-01 and 2000-01-01
                 col.push(unix_microseconds);
             }
             _ => col.push_null(),
         },
+        ColumnStorage::Floats(col) => match value {
+            Some(bytes) if bytes.len() == 4 => {
+                let val = f32::from_be_bytes(bytes.try_into().unwrap());
+                col.push(val);
+            }
+            _ => col.push_null(),
+        },
         ColumnStorage::Doubles(col) => match value {
             Some(bytes) if bytes.len() == 8 => {
                 let val = f64::from_be_bytes(bytes.try_into().unwrap());
@@ -139,10 +931,40 @@ pub fn push_column_value(column: &mut ColumnStorage, value: Option<&[u8]>) {
             }
             _ => col.push_null(),
         },
+        ColumnStorage::Numerics(col) => match value {
+            // NaN et ±Infinity (PostgreSQL 14+) n'ont pas d'équivalent décimal : NULL
+            Some(bytes) => match parse_numeric(bytes) {
+                Ok(val) => col.push(val),
+                Err(PgToPlError::NumericNotFinite) => col.push_null(),
+                Err(err) => return Err(err),
+            },
+            _ => col.push_null(),
+        },
+        ColumnStorage::Uuids(col) => match value {
+            Some(bytes) => {
+                let val = format_uuid(&types::uuid_from_sql(bytes)?);
+                col.push(val);
+            }
+            _ => col.push_null(),
+        },
+        ColumnStorage::Jsonb(col) => match value {
+            // jsonb binaire = octet de version (1) suivi du texte JSON
+            Some([1, json @ ..]) => {
+                let val = types::text_from_sql(json)?.to_string();
+                col.push(val);
+            }
+            Some(bytes) => {
+                let version = bytes.first().copied().unwrap_or_default();
+                return Err(PgToPlError::DecodeError(
+                    format!("Unsupported jsonb version: {}", version).into(),
+                ));
+            }
+            None => col.push_null(),
+        },
         ColumnStorage::TimestampsWtz(col) => match value {
             Some(bytes) if bytes.len() == 8 => {
                 let micros_pg_epoch = i64::from_be_bytes(bytes.try_into().unwrap());
-                let micros_unix_epoch = micros_pg_epoch + 946684800_000_000; // 2000-01-01 => 1970-01-01
+                let micros_unix_epoch = micros_pg_epoch + 946_684_800_000_000; // 2000-01-01 => 1970-01-01
                 col.push(micros_unix_epoch);
             }
             _ => col.push_null(),
@@ -156,56 +978,893 @@ pub fn push_column_value(column: &mut ColumnStorage, value: Option<&[u8]>) {
             }
             _ => col.push_null(),
         },
+        ColumnStorage::Inets(col) => match value {
+            Some(bytes) => col.push(format_inet(types::inet_from_sql(bytes)?, false)),
+            _ => col.push_null(),
+        },
+        ColumnStorage::Cidrs(col) => match value {
+            Some(bytes) => col.push(format_inet(types::inet_from_sql(bytes)?, true)),
+            _ => col.push_null(),
+        },
+        ColumnStorage::InetStructs(col) => match value {
+            Some(bytes) => push_inet_struct(col, bytes)?,
+            _ => push_struct_null(col)?,
+        },
+        ColumnStorage::MacAddrs(col) => match value {
+            // macaddr (6 octets) ou macaddr8 (8 octets)
+            Some(bytes) if bytes.len() == 6 || bytes.len() == 8 => {
+                let val: Vec<String> = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+                col.push(val.join(":"));
+            }
+            _ => col.push_null(),
+        },
+        ColumnStorage::TrimmedTexts(col) => match value {
+            Some(bytes) => {
+                let val = types::text_from_sql(bytes)?
+                    .trim_end_matches(' ')
+                    .to_string();
+                col.push(val);
+            }
+            _ => col.push_null(),
+        },
+        ColumnStorage::Money(col) => match value {
+            Some(bytes) if bytes.len() == 8 => {
+                let val = i64::from_be_bytes(bytes.try_into().unwrap());
+                col.values.push(val);
+            }
+            _ => col.values.push_null(),
+        },
+        ColumnStorage::Chars(col) => match value {
+            Some(bytes) => col.push(bytes.iter().map(|&b| char::from(b)).collect()),
+            _ => col.push_null(),
+        },
+        ColumnStorage::Bits(col) => match value {
+            Some(bytes) => {
+                let varbit = types::varbit_from_sql(bytes)?;
+                let val = bits_of(&varbit)
+                    .map(|bit| if bit { '1' } else { '0' })
+                    .collect();
+                col.push(val);
+            }
+            _ => col.push_null(),
+        },
+        ColumnStorage::BitLists(col) => match value {
+            Some(bytes) => {
+                let varbit = types::varbit_from_sql(bytes)?;
+                col.bits.extend(bits_of(&varbit).map(Some));
+                col.lengths.push(Some(varbit.len()));
+            }
+            _ => col.lengths.push(None),
+        },
+        ColumnStorage::TimeTzs(col) => match value {
+            Some(bytes) if bytes.len() == 12 => {
+                let (time, zone) = bytes.split_at(8);
+                // PostgreSQL stocke le décalage en secondes à l'ouest d'UTC
+                let offset = -i32::from_be_bytes(zone.try_into().unwrap());
+
+                let [time_col, offset_col] = &mut col.fields[..] else {
+                    unreachable!("timetz columns always have 2 fields");
+                };
+                push_column_value(&mut time_col.values, Some(time))?;
+                push_column_value(&mut offset_col.values, Some(&offset.to_be_bytes()))?;
+                col.validity.push(true);
+            }
+            _ => push_struct_null(col)?,
+        },
+        ColumnStorage::TimesUtc(col) => match value {
+            Some(bytes) if bytes.len() == 12 => {
+                let (time, zone) = bytes.split_at(8);
+                let micros_since_midnight = i64::from_be_bytes(time.try_into().unwrap());
+                let zone_seconds = i32::from_be_bytes(zone.try_into().unwrap()) as i64;
+
+                let utc_micros =
+                    (micros_since_midnight + zone_seconds * 1_000_000).rem_euclid(MICROS_PER_DAY);
+                col.push(utc_micros * 1000);
+            }
+            _ => col.push_null(),
+        },
+        ColumnStorage::Vectors(col) => match value {
+            Some(bytes) => {
+                let values = match col.kind {
+                    VectorKind::Vector => parse_vector(bytes)?,
+                    VectorKind::HalfVec => parse_halfvec(bytes)?,
+                    VectorKind::SparseVec => parse_sparsevec(bytes)?,
+                };
+                col.lengths.push(Some(values.len()));
+                col.values.extend(values);
+            }
+            _ => col.lengths.push(None),
+        },
+        ColumnStorage::Geometries(col) => match value {
+            Some(bytes) if col.mode == GeometryMode::Ewkb => col.wkb.push(bytes.to_vec()),
+            Some(bytes) => {
+                let (wkb, srid) = ewkb_to_wkb(bytes)?;
+                col.wkb.push(wkb);
+                col.srids.push(srid);
+            }
+            _ => {
+                col.wkb.push_null();
+                col.srids.push(None);
+            }
+        },
+        ColumnStorage::Hstores(col) => match value {
+            Some(bytes) => {
+                let mut pairs = types::hstore_from_sql(bytes)?;
+                let mut len = 0;
+                while let Some((key, value)) = pairs.next()? {
+                    col.keys.push(key.to_string());
+                    col.values.push(value.map(str::to_string));
+                    len += 1;
+                }
+                col.lengths.push(Some(len));
+            }
+            _ => col.lengths.push(None),
+        },
+        ColumnStorage::TsVectors(col) => match value {
+            Some(bytes) => {
+                let lexemes = parse_tsvector(bytes)?;
+                col.lengths.push(Some(lexemes.len()));
+                for lexeme in lexemes {
+                    col.lexemes.push(lexeme.text.to_string());
+                    col.position_counts.push(Some(lexeme.positions.len()));
+                    for (position, weight) in lexeme.positions {
+                        col.positions.push(position);
+                        col.weights.push(weight.to_string());
+                    }
+                }
+            }
+            _ => col.lengths.push(None),
+        },
+        ColumnStorage::TextFallbacks(col) => match value {
+            Some(bytes) => col.push(String::from_utf8_lossy(bytes).into_owned()),
+            _ => col.push_null(),
+        },
+        ColumnStorage::Custom(col) => match value {
+            Some(bytes) => col.decoder.decode(bytes, &mut col.values)?,
+            _ => col.values.push_null(),
+        },
+        ColumnStorage::Enums(col) => match value {
+            Some(bytes) => {
+                let val = types::text_from_sql(bytes)?.to_string();
+                col.values.push(val);
+            }
+            _ => col.values.push_null(),
+        },
+        ColumnStorage::Structs(col) => match (&mut col.anonymous, value) {
+            (Some(records), value) => records.values.push(value.map(<[u8]>::to_vec)),
+            (None, Some(bytes)) => push_record(col, bytes)?,
+            (None, None) => push_struct_null(col)?,
+        },
+        ColumnStorage::Ranges(col) => match value {
+            Some(bytes) => push_range(col, bytes)?,
+            _ => push_struct_null(col)?,
+        },
+        ColumnStorage::Multiranges(col) => match value {
+            Some(bytes) => {
+                let ranges = parse_multirange(bytes)?;
+                for range in &ranges {
+                    push_column_value(&mut col.items, Some(range))?;
+                }
+                col.lengths.push(Some(ranges.len()));
+            }
+            _ => col.lengths.push(None),
+        },
+        ColumnStorage::Geometrics(col) => match value {
+            Some(bytes) => {
+                let mut rest = bytes;
+                for field in col.fields.iter_mut() {
+                    let width = geometric_width(field.oid);
+                    if rest.len() < width {
+                        return Err(PgToPlError::NotEnoughBytes);
+                    }
+                    let (value, tail) = rest.split_at(width);
+                    push_column_value(&mut field.values, Some(value))?;
+                    rest = tail;
+                }
+                col.validity.push(true);
+            }
+            _ => push_struct_null(col)?,
+        },
+        ColumnStorage::Polygons(col) => match value {
+            Some(bytes) => push_points(col, bytes)?,
+            _ => col.lengths.push(None),
+        },
+        // chemin : octet « fermé » suivi des points comme pour un polygone
+        ColumnStorage::Paths(col) => match value {
+            Some([closed, points @ ..]) => {
+                push_points(&mut col.points, points)?;
+                col.closed.push(Some(*closed != 0));
+            }
+            _ => {
+                col.points.lengths.push(None);
+                col.closed.push(None);
+            }
+        },
+        ColumnStorage::Arrays(col) => match value {
+            Some(bytes) => {
+                let array = types::array_from_sql(bytes)?;
+                if array.element_type() != col.element_oid {
+                    return Err(PgToPlError::ArrayElementTypeMismatch(
+                        col.element_oid,
+                        array.element_type(),
+                    ));
+                }
+                let dims: Vec<ArrayDimension> = array.dimensions().collect()?;
+                if !dims.is_empty() {
+                    match col.ndim {
+                        Some(ndim) if ndim != dims.len() => {
+                            return Err(PgToPlError::ArrayDimensionMismatch(ndim, dims.len()));
+                        }
+                        Some(_) => {}
+                        None => col.ndim = Some(dims.len()),
+                    }
+                }
+
+                let mut values = array.values();
+                while let Some(element) = values.next()? {
+                    push_column_value(&mut col.values, element)?;
+                }
+                col.shapes.push(Some(dims));
+            }
+            _ => col.shapes.push(None),
+        },
     }
+    Ok(())
 }
 
-pub fn column_to_series(column: ColumnStorage) -> Series {
-    match column {
+fn bits_of<'a>(varbit: &'a types::Varbit<'a>) -> impl Iterator<Item = bool> + 'a {
+    (0..varbit.len()).map(|i| varbit.bytes()[i / 8] & (0x80 >> (i % 8)) != 0)
+}
+
+/// Nombre de points (i32) puis les points, 16 octets chacun
+fn push_points(col: &mut ItemListColumn, mut bytes: &[u8]) -> PgToPlResult<()> {
+    let count = bytes.read_i32::<BigEndian>()?.max(0) as usize;
+    if bytes.len() < count * 16 {
+        return Err(PgToPlError::NotEnoughBytes);
+    }
+    for point in bytes.chunks_exact(16).take(count) {
+        push_column_value(&mut col.items, Some(point))?;
+    }
+    col.lengths.push(Some(count));
+    Ok(())
+}
+
+fn push_struct_null(col: &mut StructColumn) -> PgToPlResult<()> {
+    for field in col.fields.iter_mut() {
+        push_column_value(&mut field.values, None)?;
+    }
+    col.validity.push(false);
+    Ok(())
+}
+
+fn push_inet_struct(col: &mut StructColumn, bytes: &[u8]) -> PgToPlResult<()> {
+    let inet = types::inet_from_sql(bytes)?;
+    let (family, addr) = match inet.addr() {
+        IpAddr::V4(addr) => (4i16, addr.octets().to_vec()),
+        IpAddr::V6(addr) => (6i16, addr.octets().to_vec()),
+    };
+
+    let [family_col, prefix_col, addr_col] = &mut col.fields[..] else {
+        unreachable!("inet columns always have 3 fields");
+    };
+    push_column_value(&mut family_col.values, Some(&family.to_be_bytes()))?;
+    push_column_value(
+        &mut prefix_col.values,
+        Some(&(inet.netmask() as i16).to_be_bytes()),
+    )?;
+    push_column_value(&mut addr_col.values, Some(&addr))?;
+    col.validity.push(true);
+    Ok(())
+}
+
+fn push_range(col: &mut StructColumn, bytes: &[u8]) -> PgToPlResult<()> {
+    let (lower, upper, empty) = match types::range_from_sql(bytes)? {
+        Range::Empty => (RangeBound::Unbounded, RangeBound::Unbounded, true),
+        Range::Nonempty(lower, upper) => (lower, upper, false),
+    };
+
+    let [lower_col, upper_col, lower_inc, upper_inc, empty_col] = &mut col.fields[..] else {
+        unreachable!("range columns always have 5 fields");
+    };
+    push_range_bound(&mut lower_col.values, &mut lower_inc.values, lower)?;
+    push_range_bound(&mut upper_col.values, &mut upper_inc.values, upper)?;
+    push_column_value(&mut empty_col.values, Some(&[empty as u8]))?;
+    col.validity.push(true);
+    Ok(())
+}
+
+/// Borne infinie (ou range vide) : valeur NULL, non inclusive
+fn push_range_bound(
+    value: &mut ColumnStorage,
+    inclusive: &mut ColumnStorage,
+    bound: RangeBound<Option<&[u8]>>,
+) -> PgToPlResult<()> {
+    let (bound_value, bound_inclusive) = match bound {
+        RangeBound::Inclusive(bound_value) => (bound_value, true),
+        RangeBound::Exclusive(bound_value) => (bound_value, false),
+        RangeBound::Unbounded => (None, false),
+    };
+    push_column_value(value, bound_value)?;
+    push_column_value(inclusive, Some(&[bound_inclusive as u8]))
+}
+
+fn push_record(col: &mut StructColumn, bytes: &[u8]) -> PgToPlResult<()> {
+    let values = parse_record(bytes)?;
+    if values.len() != col.fields.len() {
+        return Err(PgToPlError::RecordFieldCountMismatch(
+            col.fields.len(),
+            values.len(),
+        ));
+    }
+
+    for (field, (oid, value)) in col.fields.iter_mut().zip(values) {
+        if field.oid != oid {
+            return Err(PgToPlError::RecordFieldTypeMismatch(field.oid, oid));
+        }
+        push_column_value(&mut field.values, value)?;
+    }
+    col.validity.push(true);
+    Ok(())
+}
+
+/// Champs f1, f2, ... d'un record anonyme, d'après les OID de sa première valeur
+fn anonymous_fields(
+    values: &[RecordField<'_>],
+    options: &DecodeOptions,
+    types: &TypeCatalog,
+    null_rows: usize,
+) -> PgToPlResult<Vec<StructField>> {
+    let mut fields = Vec::with_capacity(values.len());
+    for (i, (oid, _)) in values.iter().enumerate() {
+        let mut field = StructField {
+            oid: *oid,
+            values: column_or_bytes(format!("f{}", i + 1), *oid, options, types),
+        };
+        for _ in 0..null_rows {
+            push_column_value(&mut field.values, None)?;
+        }
+        fields.push(field);
+    }
+    Ok(fields)
+}
+
+/// OID des champs des records anonymes en attente de décodage, `None` s'il n'y en a pas.
+/// Les records imbriqués ne sont connus qu'une fois leur parent décodé.
+pub fn pending_record_oids(columns: &mut [ColumnStorage]) -> PgToPlResult<Option<Vec<Oid>>> {
+    let mut oids = Vec::new();
+    let mut pending = false;
+    let mut result = Ok(());
+    for column in columns.iter_mut() {
+        visit_storages(column, &mut |column| {
+            if let ColumnStorage::Structs(StructColumn {
+                anonymous: Some(records),
+                ..
+            }) = column
+                && !records.values.is_empty()
+            {
+                pending = true;
+                if let Some(bytes) = records.values.iter().flatten().next() {
+                    match parse_record(bytes) {
+                        Ok(fields) => oids.extend(fields.iter().map(|(oid, _)| *oid)),
+                        Err(err) => result = Err(err),
+                    }
+                }
+            }
+        });
+    }
+    result?;
+    Ok(pending.then_some(oids))
+}
+
+/// Décode les records anonymes en attente avec les types du catalogue
+pub fn decode_pending_records(
+    columns: &mut [ColumnStorage],
+    types: &TypeCatalog,
+) -> PgToPlResult<()> {
+    let mut result = Ok(());
+    for column in columns.iter_mut() {
+        visit_storages(column, &mut |column| {
+            if let ColumnStorage::Structs(col) = column
+                && result.is_ok()
+            {
+                result = decode_records(col, types);
+            }
+        });
+    }
+    result
+}
+
+fn decode_records(col: &mut StructColumn, types: &TypeCatalog) -> PgToPlResult<()> {
+    let Some(records) = col.anonymous.as_mut() else {
+        return Ok(());
+    };
+    let values = std::mem::take(&mut records.values);
+    let options = records.options.clone();
+    for value in values {
+        match value {
+            Some(bytes) => {
+                if col.fields.is_empty() {
+                    let fields = parse_record(&bytes)?;
+                    col.fields = anonymous_fields(&fields, &options, types, col.validity.len())?;
+                }
+                push_record(col, &bytes)?;
+            }
+            None => push_struct_null(col)?,
+        }
+    }
+    Ok(())
+}
+
+pub fn column_to_series(column: ColumnStorage) -> PgToPlResult<Series> {
+    let series = match column {
+        ColumnStorage::SmallInts(col) => Series::new(col.name.into(), &col.data),
         ColumnStorage::Ints(col) => Series::new(col.name.into(), &col.data),
+        ColumnStorage::BigInts(col) => Series::new(col.name.into(), &col.data),
         ColumnStorage::Texts(col) => Series::new(col.name.into(), &col.data),
         ColumnStorage::Bools(col) => Series::new(col.name.into(), &col.data),
         ColumnStorage::Bytes(col) => Series::new(col.name.into(), &col.data),
         ColumnStorage::Dates(col) => Series::new(col.name.into(), &col.data)
             .cast(&DataType::Date)
             .unwrap(),
-        ColumnStorage::TextArray(col) => text_array_to_series(col.name.as_str(), col.data),
         ColumnStorage::Timestamps(col) => Series::new(col.name.into(), &col.data)
             .cast(&DataType::Datetime(TimeUnit::Microseconds, None))
             .unwrap(),
+        ColumnStorage::Floats(col) => Series::new(col.name.into(), &col.data),
         ColumnStorage::Doubles(col) => Series::new(col.name.into(), &col.data),
+        ColumnStorage::Numerics(col) => numerics_to_series(col)?,
+        ColumnStorage::Uuids(col) => Series::new(col.name.into(), &col.data),
+        ColumnStorage::Jsonb(col) => Series::new(col.name.into(), &col.data),
         ColumnStorage::TimestampsWtz(col) => Series::new(col.name.into(), &col.data)
             .cast(&DataType::Datetime(TimeUnit::Microseconds, None))
             .unwrap(),
         ColumnStorage::Times(col) => Series::new(col.name.into(), &col.data)
             .cast(&DataType::Time)
             .unwrap(),
+        ColumnStorage::Arrays(col) => list_to_series(col)?,
+        ColumnStorage::Enums(col) => enum_to_series(col)?,
+        ColumnStorage::Structs(col) => struct_to_series(col)?,
+        ColumnStorage::Ranges(col) => struct_to_series(col)?,
+        ColumnStorage::Inets(col) => Series::new(col.name.into(), &col.data),
+        ColumnStorage::Cidrs(col) => Series::new(col.name.into(), &col.data),
+        ColumnStorage::InetStructs(col) => struct_to_series(col)?,
+        ColumnStorage::MacAddrs(col) => Series::new(col.name.into(), &col.data),
+        ColumnStorage::TrimmedTexts(col) => Series::new(col.name.into(), &col.data),
+        ColumnStorage::Money(col) => {
+            let values: Int128Chunked = col
+                .values
+                .data
+                .into_iter()
+                .map(|val| val.map(i128::from))
+                .collect();
+            values
+                .into_decimal_unchecked(None, col.scale)
+                .into_series()
+                .with_name(col.values.name.into())
+        }
+        ColumnStorage::Chars(col) => Series::new(col.name.into(), &col.data),
+        ColumnStorage::Bits(col) => Series::new(col.name.into(), &col.data),
+        ColumnStorage::BitLists(col) => {
+            let bits = Series::new("".into(), &col.bits);
+            group_into_lists(&col.name, &bits, col.lengths.into_iter())
+        }
+        ColumnStorage::TimeTzs(col) => struct_to_series(col)?,
+        ColumnStorage::Custom(col) => col.values.into_series(&col.name, &col.decoder.dtype())?,
+        ColumnStorage::Vectors(col) => vectors_to_series(col),
+        ColumnStorage::TextFallbacks(col) => Series::new(col.name.into(), &col.data),
+        ColumnStorage::Geometries(col) => geometries_to_series(col)?,
+        ColumnStorage::Hstores(col) => {
+            let fields = [
+                Series::new("key".into(), &col.keys),
+                Series::new("value".into(), &col.values),
+            ];
+            entries_to_series(&col.name, &fields, col.lengths)
+        }
+        ColumnStorage::TsVectors(col) => {
+            let positions = Series::new("".into(), &col.positions);
+            let weights = Series::new("".into(), &col.weights);
+            let fields = [
+                Series::new("lexeme".into(), &col.lexemes),
+                group_into_lists("positions", &positions, col.position_counts.iter().copied()),
+                group_into_lists("weights", &weights, col.position_counts.into_iter()),
+            ];
+            entries_to_series(&col.name, &fields, col.lengths)
+        }
+        ColumnStorage::TimesUtc(col) => Series::new(col.name.into(), &col.data)
+            .cast(&DataType::Time)
+            .unwrap(),
+        ColumnStorage::Multiranges(col) | ColumnStorage::Polygons(col) => {
+            let items = column_to_series(*col.items)?;
+            group_into_lists(&col.name, &items, col.lengths.into_iter())
+        }
+        ColumnStorage::Paths(col) => {
+            let validity: Vec<bool> = col.closed.iter().map(Option::is_some).collect();
+            let fields = [
+                Series::new("closed".into(), &col.closed),
+                column_to_series(ColumnStorage::Polygons(col.points))?,
+            ];
+            let series =
+                StructChunked::from_series(col.name.into(), validity.len(), fields.iter())?
+                    .into_series();
+            with_struct_validity(series, &validity)?
+        }
+        ColumnStorage::Geometrics(col) => struct_to_series(col)?,
+    };
+    Ok(series)
+}
+
+/// `Array(Float32, dim)` si tous les vecteurs ont la même dimension, `List<Float32>` sinon
+fn vectors_to_series(col: VectorColumn) -> Series {
+    let mut dims = col.lengths.iter().flatten();
+    let uniform_dim = dims
+        .next()
+        .copied()
+        .filter(|dim| dims.all(|other| other == dim));
+
+    let values = Series::new("".into(), &col.values);
+    let series = group_into_lists(&col.name, &values, col.lengths.into_iter());
+    match uniform_dim {
+        Some(dim) => series
+            .cast(&DataType::Array(Box::new(DataType::Float32), dim))
+            .unwrap(),
+        None => series,
     }
 }
 
-pub fn text_array_to_series(name: &str, data: Vec<Option<Vec<Option<String>>>>) -> Series {
-    let list_chunked: ListChunked = data
+/// `List<Struct>` à partir des champs à plat et du nombre d'entrées par ligne
+fn entries_to_series(name: &str, fields: &[Series], lengths: Vec<Option<usize>>) -> Series {
+    let len = fields.first().map_or(0, |field| field.len());
+    let entries = StructChunked::from_series("".into(), len, fields.iter())
+        .unwrap()
+        .into_series();
+    group_into_lists(name, &entries, lengths.into_iter())
+}
+
+fn struct_to_series(col: StructColumn) -> PgToPlResult<Series> {
+    let fields = col
+        .fields
+        .into_iter()
+        .map(|field| column_to_series(field.values))
+        .collect::<PgToPlResult<Vec<_>>>()?;
+
+    let series = StructChunked::from_series(col.name.into(), col.validity.len(), fields.iter())
+        .unwrap()
+        .into_series();
+    with_struct_validity(series, &col.validity)
+}
+
+/// Lignes NULL : la struct entière est nulle, pas seulement ses champs
+fn with_struct_validity(series: Series, validity: &[bool]) -> PgToPlResult<Series> {
+    if validity.iter().all(|valid| *valid) {
+        return Ok(series);
+    }
+    let mask = BooleanChunked::new("".into(), validity);
+    let nulls = Series::full_null(series.name().clone(), 1, series.dtype());
+    Ok(series.zip_with(&mask, &nulls)?)
+}
+
+fn geometries_to_series(col: GeometryColumn) -> PgToPlResult<Series> {
+    let wkb = Series::new(col.wkb.name.as_str().into(), &col.wkb.data);
+    if col.mode != GeometryMode::Struct {
+        return Ok(wkb);
+    }
+
+    let validity: Vec<bool> = col.wkb.data.iter().map(Option::is_some).collect();
+    let fields = [
+        wkb.with_name("wkb".into()),
+        Series::new("srid".into(), &col.srids),
+    ];
+    let series = StructChunked::from_series(col.wkb.name.into(), validity.len(), fields.iter())?
+        .into_series();
+    with_struct_validity(series, &validity)
+}
+
+/// Un libellé absent des catégories est une erreur, pas un NULL
+fn enum_to_series(col: EnumColumn) -> PgToPlResult<Series> {
+    let dtype = match col.mode {
+        EnumMode::Enum => {
+            if let Some(label) = col
+                .values
+                .data
+                .iter()
+                .flatten()
+                .find(|label| !col.categories.contains(label))
+            {
+                return Err(PgToPlError::UnknownEnumLabel(label.clone()));
+            }
+            DataType::from_frozen_categories(FrozenCategories::new(
+                col.categories.iter().map(String::as_str),
+            )?)
+        }
+        EnumMode::Categorical => DataType::from_categories(Categories::global()),
+    };
+
+    Ok(Series::new(col.values.name.into(), &col.values.data).strict_cast(&dtype)?)
+}
+
+/// Ramène toutes les valeurs à la plus grande échelle rencontrée
+/// Une valeur qui ne tient plus dans 128 bits à cette échelle est une erreur, pas un NULL
+fn numerics_to_series(col: ColumnResult<Numeric>) -> PgToPlResult<Series> {
+    let scale = col
+        .data
+        .iter()
+        .flatten()
+        .map(|val| val.scale)
+        .max()
+        .unwrap_or(0);
+    let values = col
+        .data
         .into_iter()
-        .map(|maybe_vec| maybe_vec.map(|v| Series::new("".into(), v)))
+        .map(|val| {
+            val.map(|val| val.rescale(scale).ok_or(PgToPlError::NumericOverflow))
+                .transpose()
+        })
+        .collect::<PgToPlResult<Int128Chunked>>()?;
+
+    Ok(values
+        .into_decimal_unchecked(None, scale as usize)
+        .into_series()
+        .with_name(col.name.into()))
+}
+
+fn list_to_series(col: ListColumn) -> PgToPlResult<Series> {
+    let ndim = col.ndim.unwrap_or(1);
+    let mut series = column_to_series(*col.values)?;
+
+    // Niveaux internes, du plus profond vers l'extérieur : une ligne de dimensions
+    // [n0, n1, ...] contribue n0 * ... * n(k-1) listes de longueur nk au niveau k
+    for level in (1..ndim).rev() {
+        let lengths = col
+            .shapes
+            .iter()
+            .flatten()
+            .filter(|dims| !dims.is_empty())
+            .flat_map(|dims| {
+                let count = dims[..level].iter().map(|d| d.len as usize).product();
+                std::iter::repeat_n(Some(dims[level].len as usize), count)
+            });
+        series = group_into_lists("", &series, lengths);
+    }
+
+    let lengths = col.shapes.iter().map(|shape| {
+        shape
+            .as_ref()
+            .map(|dims| dims.first().map_or(0, |d| d.len as usize))
+    });
+    let series = group_into_lists(&col.name, &series, lengths);
+
+    Ok(match col.mode {
+        ArrayMode::FixedWhenUniform => match uniform_dims(&col.shapes) {
+            Some(dims) => {
+                let dtype = dims
+                    .iter()
+                    .rev()
+                    .fold(series.dtype().leaf_dtype().clone(), |inner, &len| {
+                        DataType::Array(Box::new(inner), len)
+                    });
+                series.cast(&dtype).unwrap()
+            }
+            None => series,
+        },
+        ArrayMode::List => series,
+    })
+}
+
+/// Regroupe les valeurs consécutives de `values` en listes de longueurs `lengths`
+fn group_into_lists<I>(name: &str, values: &Series, lengths: I) -> Series
+where
+    I: Iterator<Item = Option<usize>>,
+{
+    let mut builder = get_list_builder(
+        values.dtype(),
+        values.len(),
+        lengths.size_hint().0,
+        name.into(),
+    );
+
+    let mut offset = 0;
+    for length in lengths {
+        match length {
+            Some(len) => {
+                builder
+                    .append_series(&values.slice(offset as i64, len))
+                    .unwrap();
+                offset += len;
+            }
+            None => builder.append_null(),
+        }
+    }
+
+    builder.finish().into_series()
+}
+
+/// Dimensions communes à toutes les lignes non NULL, `None` si elles diffèrent
+fn uniform_dims(shapes: &[Option<Vec<ArrayDimension>>]) -> Option<Vec<usize>> {
+    let mut uniform: Option<&Vec<ArrayDimension>> = None;
+    for dims in shapes.iter().flatten() {
+        match uniform {
+            _ if dims.is_empty() => return None,
+            Some(first) if first.iter().map(|d| d.len).ne(dims.iter().map(|d| d.len)) => {
+                return None;
+            }
+            Some(_) => {}
+            None => uniform = Some(dims),
+        }
+    }
+    uniform.map(|dims| dims.iter().map(|d| d.len as usize).collect())
+}
+
+fn lower_bounds_to_series(col: &ListColumn) -> Series {
+    let lower_bounds: ListChunked = col
+        .shapes
+        .iter()
+        .map(|shape| {
+            shape.as_ref().map(|dims| {
+                let bounds: Vec<i32> = dims.iter().map(|d| d.lower_bound).collect();
+                Series::new("".into(), bounds)
+            })
+        })
         .collect();
 
-    list_chunked.into_series().with_name(name.into())
+    lower_bounds
+        .into_series()
+        .cast(&DataType::List(Box::new(DataType::Int32)))
+        .unwrap()
+        .with_name(format!("{}_lower_bounds", col.name).into())
 }
 
-pub fn clone_storages(columns: &Vec<ColumnStorage>) -> Vec<ColumnStorage> {
-    let mut copy = Vec::with_capacity(columns.len());
+/// Convertit les colonnes en DataFrame, en ajoutant les colonnes de métadonnées demandées
+pub fn columns_to_dataframe(columns: Vec<ColumnStorage>) -> PgToPlResult<DataFrame> {
+    let mut series = Vec::with_capacity(columns.len());
     for column in columns {
-        copy.push(match column {
-            ColumnStorage::Ints(col) => ColumnStorage::Ints(col.clone_empty()),
-            ColumnStorage::Texts(col) => ColumnStorage::Texts(col.clone_empty()),
-            ColumnStorage::Bools(col) => ColumnStorage::Bools(col.clone_empty()),
-            ColumnStorage::Bytes(col) => ColumnStorage::Bytes(col.clone_empty()),
-            ColumnStorage::Dates(col) => ColumnStorage::Dates(col.clone_empty()),
-            ColumnStorage::TextArray(col) => ColumnStorage::TextArray(col.clone_empty()),
-            ColumnStorage::Timestamps(col) => ColumnStorage::Timestamps(col.clone_empty()),
-            ColumnStorage::Doubles(col) => ColumnStorage::Doubles(col.clone_empty()),
-            ColumnStorage::TimestampsWtz(col) => ColumnStorage::TimestampsWtz(col.clone_empty()),
-            ColumnStorage::Times(col) => ColumnStorage::Times(col.clone_empty()),
+        let companion = match &column {
+            ColumnStorage::Arrays(col) if col.lower_bounds => Some(lower_bounds_to_series(col)),
+            ColumnStorage::Geometries(col) if col.mode == GeometryMode::Wkb => Some(Series::new(
+                format!("{}_srid", col.wkb.name).into(),
+                &col.srids,
+            )),
+            _ => None,
+        };
+        series.push(column_to_series(column)?);
+        series.extend(companion);
+    }
+
+    // une colonne compagnon peut porter le nom d'une colonne de la requête
+    let mut names = HashSet::with_capacity(series.len());
+    if let Some(duplicate) = series.iter().find(|s| !names.insert(s.name().clone())) {
+        return Err(PgToPlError::DuplicateColumn(duplicate.name().to_string()));
+    }
+    Ok(DataFrame::new(
+        series.into_iter().map(Column::from).collect(),
+    )?)
+}
+
+fn format_uuid(bytes: &[u8; 16]) -> String {
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Même rendu que PostgreSQL : le préfixe d'un inet n'est affiché que s'il ne couvre pas toute l'adresse
+fn format_inet(inet: types::Inet, cidr: bool) -> String {
+    let full_mask = match inet.addr() {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    };
+    if cidr || inet.netmask() != full_mask {
+        format!("{}/{}", inet.addr(), inet.netmask())
+    } else {
+        inet.addr().to_string()
+    }
+}
+
+/// Appelle `f` sur chaque stockage imbriqué (tableaux, structures, listes) puis sur `column`
+fn visit_storages(column: &mut ColumnStorage, f: &mut impl FnMut(&mut ColumnStorage)) {
+    match column {
+        ColumnStorage::Arrays(col) => visit_storages(&mut col.values, f),
+        ColumnStorage::Structs(col)
+        | ColumnStorage::Ranges(col)
+        | ColumnStorage::Geometrics(col)
+        | ColumnStorage::InetStructs(col)
+        | ColumnStorage::TimeTzs(col) => {
+            for field in col.fields.iter_mut() {
+                visit_storages(&mut field.values, f);
+            }
+        }
+        ColumnStorage::Multiranges(col) | ColumnStorage::Polygons(col) => {
+            visit_storages(&mut col.items, f)
+        }
+        ColumnStorage::Paths(col) => visit_storages(&mut col.points.items, f),
+        _ => {}
+    }
+    f(column);
+}
+
+fn visit_enums(column: &mut ColumnStorage, f: &mut impl FnMut(&mut EnumColumn)) {
+    visit_storages(column, &mut |column| {
+        if let ColumnStorage::Enums(col) = column {
+            f(col);
+        }
+    });
+}
+
+/// OID des enums dont un libellé reçu est absent du cache
+pub fn stale_enum_oids(columns: &mut [ColumnStorage]) -> Vec<Oid> {
+    let mut oids = Vec::new();
+    for column in columns.iter_mut() {
+        visit_enums(column, &mut |col| {
+            if col.has_unknown_label() && !oids.contains(&col.oid) {
+                oids.push(col.oid);
+            }
+        });
+    }
+    oids
+}
+
+/// Remplace les catégories des colonnes enum par celles du catalogue
+pub fn refresh_enum_labels(columns: &mut [ColumnStorage], types: &TypeCatalog) {
+    for column in columns.iter_mut() {
+        visit_enums(column, &mut |col| {
+            if let Some(pg_type) = types.get(col.oid) {
+                col.categories = pg_type.enum_labels.clone();
+            }
         });
     }
-    copy
+}
+
+pub fn clone_storage(column: &ColumnStorage) -> ColumnStorage {
+    match column {
+        ColumnStorage::SmallInts(col) => ColumnStorage::SmallInts(col.clone_empty()),
+        ColumnStorage::Ints(col) => ColumnStorage::Ints(col.clone_empty()),
+        ColumnStorage::BigInts(col) => ColumnStorage::BigInts(col.clone_empty()),
+        ColumnStorage::Texts(col) => ColumnStorage::Texts(col.clone_empty()),
+        ColumnStorage::Bools(col) => ColumnStorage::Bools(col.clone_empty()),
+        ColumnStorage::Bytes(col) => ColumnStorage::Bytes(col.clone_empty()),
+        ColumnStorage::Dates(col) => ColumnStorage::Dates(col.clone_empty()),
+        ColumnStorage::Timestamps(col) => ColumnStorage::Timestamps(col.clone_empty()),
+        ColumnStorage::Floats(col) => ColumnStorage::Floats(col.clone_empty()),
+        ColumnStorage::Doubles(col) => ColumnStorage::Doubles(col.clone_empty()),
+        ColumnStorage::Numerics(col) => ColumnStorage::Numerics(col.clone_empty()),
+        ColumnStorage::Uuids(col) => ColumnStorage::Uuids(col.clone_empty()),
+        ColumnStorage::Jsonb(col) => ColumnStorage::Jsonb(col.clone_empty()),
+        ColumnStorage::TimestampsWtz(col) => ColumnStorage::TimestampsWtz(col.clone_empty()),
+        ColumnStorage::Times(col) => ColumnStorage::Times(col.clone_empty()),
+        ColumnStorage::Arrays(col) => ColumnStorage::Arrays(col.clone_empty()),
+        ColumnStorage::Enums(col) => ColumnStorage::Enums(col.clone_empty()),
+        ColumnStorage::Structs(col) => ColumnStorage::Structs(col.clone_empty()),
+        ColumnStorage::Ranges(col) => ColumnStorage::Ranges(col.clone_empty()),
+        ColumnStorage::Multiranges(col) => ColumnStorage::Multiranges(col.clone_empty()),
+        ColumnStorage::Geometrics(col) => ColumnStorage::Geometrics(col.clone_empty()),
+        ColumnStorage::Polygons(col) => ColumnStorage::Polygons(col.clone_empty()),
+        ColumnStorage::Paths(col) => ColumnStorage::Paths(col.clone_empty()),
+        ColumnStorage::Inets(col) => ColumnStorage::Inets(col.clone_empty()),
+        ColumnStorage::Cidrs(col) => ColumnStorage::Cidrs(col.clone_empty()),
+        ColumnStorage::InetStructs(col) => ColumnStorage::InetStructs(col.clone_empty()),
+        ColumnStorage::MacAddrs(col) => ColumnStorage::MacAddrs(col.clone_empty()),
+        ColumnStorage::TrimmedTexts(col) => ColumnStorage::TrimmedTexts(col.clone_empty()),
+        ColumnStorage::Money(col) => ColumnStorage::Money(MoneyColumn {
+            values: col.values.clone_empty(),
+            scale: col.scale,
+        }),
+        ColumnStorage::Chars(col) => ColumnStorage::Chars(col.clone_empty()),
+        ColumnStorage::Bits(col) => ColumnStorage::Bits(col.clone_empty()),
+        ColumnStorage::BitLists(col) => ColumnStorage::BitLists(col.clone_empty()),
+        ColumnStorage::TimeTzs(col) => ColumnStorage::TimeTzs(col.clone_empty()),
+        ColumnStorage::TimesUtc(col) => ColumnStorage::TimesUtc(col.clone_empty()),
+        ColumnStorage::Custom(col) => ColumnStorage::Custom(col.clone_empty()),
+        ColumnStorage::TextFallbacks(col) => ColumnStorage::TextFallbacks(col.clone_empty()),
+        ColumnStorage::Vectors(col) => ColumnStorage::Vectors(col.clone_empty()),
+        ColumnStorage::Geometries(col) => ColumnStorage::Geometries(col.clone_empty()),
+        ColumnStorage::Hstores(col) => ColumnStorage::Hstores(col.clone_empty()),
+        ColumnStorage::TsVectors(col) => ColumnStorage::TsVectors(col.clone_empty()),
+    }
+}
+
+pub fn clone_storages(columns: &[ColumnStorage]) -> Vec<ColumnStorage> {
+    columns.iter().map(clone_storage).collect()
 }
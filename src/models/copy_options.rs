@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+
+/// Options de `Client::copy_in`
+#[derive(Debug, Clone, Default)]
+pub struct CopyInOptions {
+    /// Colonnes du DataFrame à écrire, toutes par défaut
+    pub columns: Option<Vec<String>>,
+    /// Colonne du DataFrame → colonne de la table, même nom par défaut
+    pub mapping: HashMap<String, String>,
+}
+
+impl CopyInOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn with_mapping(mut self, column: &str, table_column: &str) -> Self {
+        self.mapping
+            .insert(column.to_string(), table_column.to_string());
+        self
+    }
+}
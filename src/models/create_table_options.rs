@@ -0,0 +1,87 @@
+/// Type de table créée
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TablePersistence {
+    #[default]
+    Permanent,
+    /// `CREATE TEMPORARY TABLE`, supprimée en fin de session
+    Temporary,
+    /// `CREATE UNLOGGED TABLE`, hors WAL
+    Unlogged,
+}
+
+/// Type PostgreSQL des colonnes `Struct`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StructMode {
+    #[default]
+    Jsonb,
+    /// Type composite `<table>_<colonne>` créé avant la table, dans `pg_temp` si la table
+    /// est temporaire
+    Composite,
+}
+
+/// Options de `Client::create_table_from_schema`
+#[derive(Debug, Clone)]
+pub struct CreateTableOptions {
+    pub schema: Option<String>,
+    pub primary_key: Vec<String>,
+    /// Table existante laissée telle quelle ; un type composite existant n'est réutilisé
+    /// que s'il a les mêmes attributs, sinon erreur
+    pub if_not_exists: bool,
+    pub persistence: TablePersistence,
+    pub struct_mode: StructMode,
+    /// Noms entre guillemets ; sinon insérés tels quels et soumis aux règles de casse de PostgreSQL
+    pub quote_identifiers: bool,
+}
+
+impl Default for CreateTableOptions {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            primary_key: Vec::new(),
+            if_not_exists: false,
+            persistence: TablePersistence::default(),
+            struct_mode: StructMode::default(),
+            quote_identifiers: true,
+        }
+    }
+}
+
+impl CreateTableOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_schema(mut self, schema: &str) -> Self {
+        self.schema = Some(schema.to_string());
+        self
+    }
+
+    pub fn with_primary_key<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.primary_key = columns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_if_not_exists(mut self, if_not_exists: bool) -> Self {
+        self.if_not_exists = if_not_exists;
+        self
+    }
+
+    pub fn with_persistence(mut self, persistence: TablePersistence) -> Self {
+        self.persistence = persistence;
+        self
+    }
+
+    pub fn with_struct_mode(mut self, struct_mode: StructMode) -> Self {
+        self.struct_mode = struct_mode;
+        self
+    }
+
+    pub fn with_quote_identifiers(mut self, quote_identifiers: bool) -> Self {
+        self.quote_identifiers = quote_identifiers;
+        self
+    }
+}
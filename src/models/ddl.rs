@@ -0,0 +1,192 @@
+use polars::prelude::{DataType, Schema};
+
+use crate::models::create_table_options::{CreateTableOptions, StructMode, TablePersistence};
+use crate::utils::error::{PgToPlError, PgToPlResult};
+use crate::utils::quote_identifier;
+
+/// Instructions `CREATE TYPE` des composites éventuels puis `CREATE TABLE`
+pub fn create_table_statements(
+    name: &str,
+    schema: &Schema,
+    options: &CreateTableOptions,
+) -> PgToPlResult<Vec<String>> {
+    let mut ddl = Ddl {
+        options,
+        statements: Vec::new(),
+    };
+
+    let mut definitions = schema
+        .iter()
+        .map(|(column, dtype)| {
+            let pg_type = ddl.column_type(dtype, &format!("{}_{}", name, column))?;
+            Ok(format!("{} {}", ddl.identifier(column), pg_type))
+        })
+        .collect::<PgToPlResult<Vec<_>>>()?;
+    if !options.primary_key.is_empty() {
+        let keys: Vec<String> = options
+            .primary_key
+            .iter()
+            .map(|key| ddl.identifier(key))
+            .collect();
+        definitions.push(format!("PRIMARY KEY ({})", keys.join(", ")));
+    }
+
+    let persistence = match options.persistence {
+        TablePersistence::Permanent => "",
+        TablePersistence::Temporary => "TEMPORARY ",
+        TablePersistence::Unlogged => "UNLOGGED ",
+    };
+    let if_not_exists = if options.if_not_exists {
+        "IF NOT EXISTS "
+    } else {
+        ""
+    };
+    let table = format!(
+        "CREATE {}TABLE {}{} ({})",
+        persistence,
+        if_not_exists,
+        ddl.qualified(name),
+        definitions.join(", ")
+    );
+    ddl.statements.push(table);
+    Ok(ddl.statements)
+}
+
+/// Type PostgreSQL d'une colonne Polars, `None` pour les structures et types objets
+pub fn pg_type_name(dtype: &DataType) -> Option<String> {
+    let name = match dtype {
+        DataType::Boolean => "boolean",
+        DataType::Int8 | DataType::Int16 | DataType::UInt8 => "smallint",
+        DataType::Int32 | DataType::UInt16 => "integer",
+        DataType::Int64 | DataType::UInt32 => "bigint",
+        DataType::UInt64 => "numeric(20, 0)",
+        DataType::Float32 => "real",
+        DataType::Float64 => "double precision",
+        DataType::String | DataType::Categorical(_, _) | DataType::Enum(_, _) | DataType::Null => {
+            "text"
+        }
+        DataType::Binary => "bytea",
+        DataType::Date => "date",
+        DataType::Time => "time",
+        DataType::Datetime(_, None) => "timestamp",
+        DataType::Datetime(_, Some(_)) => "timestamptz",
+        DataType::Duration(_) => "interval",
+        DataType::Decimal(precision, scale) => {
+            return Some(match (precision, scale) {
+                (Some(precision), scale) => {
+                    format!("numeric({}, {})", precision, scale.unwrap_or(0))
+                }
+                // précision maximale de Polars, pour garder l'échelle
+                (None, Some(scale)) => format!("numeric(38, {})", scale),
+                (None, None) => String::from("numeric"),
+            });
+        }
+        // les tableaux PostgreSQL ont le même type quel que soit leur nombre de dimensions
+        DataType::List(inner) | DataType::Array(inner, _) => {
+            return pg_type_name(inner).map(array_type);
+        }
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+fn array_type(element: String) -> String {
+    if element.ends_with("[]") {
+        element
+    } else {
+        format!("{}[]", element)
+    }
+}
+
+/// Type témoin des composites `IF NOT EXISTS`, supprimé aussitôt comparé
+const EXPECTED_TYPE: &str = "pg_to_pl_expected_type";
+
+/// Attributs (nom, type, modificateur) d'un type composite, dans l'ordre
+fn type_attributes(type_name: &str) -> String {
+    format!(
+        "SELECT array_agg((attname, atttypid, atttypmod) ORDER BY attnum) FROM pg_attribute \
+        WHERE attrelid = (SELECT typrelid FROM pg_type WHERE oid = '{}'::regtype) \
+        AND attnum > 0 AND NOT attisdropped",
+        type_name
+    )
+}
+
+struct Ddl<'a> {
+    options: &'a CreateTableOptions,
+    statements: Vec<String>,
+}
+
+impl Ddl<'_> {
+    fn identifier(&self, name: &str) -> String {
+        if self.options.quote_identifiers {
+            quote_identifier(name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn qualified(&self, name: &str) -> String {
+        match &self.options.schema {
+            Some(schema) => format!("{}.{}", self.identifier(schema), self.identifier(name)),
+            None => self.identifier(name),
+        }
+    }
+
+    /// `type_name` : nom du composite si la colonne est une structure
+    fn column_type(&mut self, dtype: &DataType, type_name: &str) -> PgToPlResult<String> {
+        match dtype {
+            DataType::Struct(fields) => match self.options.struct_mode {
+                StructMode::Jsonb => Ok(String::from("jsonb")),
+                StructMode::Composite => {
+                    let attributes = fields
+                        .iter()
+                        .map(|field| {
+                            let pg_type = self.column_type(
+                                field.dtype(),
+                                &format!("{}_{}", type_name, field.name()),
+                            )?;
+                            Ok(format!("{} {}", self.identifier(field.name()), pg_type))
+                        })
+                        .collect::<PgToPlResult<Vec<_>>>()?;
+                    // type temporaire lui aussi, supprimé avec la session comme la table
+                    let qualified = match self.options.persistence {
+                        TablePersistence::Temporary => {
+                            format!("pg_temp.{}", self.identifier(type_name))
+                        }
+                        _ => self.qualified(type_name),
+                    };
+                    let create =
+                        format!("CREATE TYPE {} AS ({})", qualified, attributes.join(", "));
+                    // pas de IF NOT EXISTS pour CREATE TYPE : un type existant n'est réutilisé
+                    // que s'il a les mêmes attributs, comparés à un type témoin temporaire
+                    self.statements.push(if self.options.if_not_exists {
+                        let existing = qualified.replace('\'', "''");
+                        format!(
+                            "DO $$ BEGIN {}; EXCEPTION WHEN duplicate_object THEN \
+                            CREATE TYPE pg_temp.{} AS ({}); \
+                            IF ({}) IS DISTINCT FROM ({}) THEN \
+                            RAISE EXCEPTION 'type % already exists with different attributes', '{}'; \
+                            END IF; DROP TYPE pg_temp.{}; END $$",
+                            create,
+                            EXPECTED_TYPE,
+                            attributes.join(", "),
+                            type_attributes(&existing),
+                            type_attributes(&format!("pg_temp.{}", EXPECTED_TYPE)),
+                            existing,
+                            EXPECTED_TYPE,
+                        )
+                    } else {
+                        create
+                    });
+                    Ok(qualified)
+                }
+            },
+            DataType::List(inner) | DataType::Array(inner, _) => {
+                let element = self.column_type(inner, type_name)?;
+                Ok(array_type(element))
+            }
+            _ => pg_type_name(dtype)
+                .ok_or_else(|| PgToPlError::UnsupportedDataType(dtype.to_string())),
+        }
+    }
+}
@@ -1,6 +1,13 @@
 pub mod client;
 pub mod client_options;
 pub mod column_result;
+pub mod copy_options;
+pub mod create_table_options;
+pub mod ddl;
 pub mod params;
 pub mod pool;
 pub mod pool_options;
+pub mod to_param;
+pub mod type_catalog;
+pub mod type_decoder;
+pub mod upsert;
@@ -1,5 +1,20 @@
-use postgres_protocol::Oid;
+use std::net::IpAddr;
 
+use bytes::BytesMut;
+use postgres_protocol::{Oid, types};
+
+use crate::{
+    PgToPlError,
+    models::column_result::{array_element_oid, is_builtin_oid, range_subtype_oid},
+    utils::{error::PgToPlResult, numeric::encode_numeric},
+};
+
+/// Microsecondes entre l'epoch Unix et l'epoch PostgreSQL (2000-01-01)
+const PG_EPOCH_MICROS: i64 = 946_684_800_000_000;
+/// Jours entre l'epoch Unix et l'epoch PostgreSQL (2000-01-01)
+const PG_EPOCH_DAYS: i32 = 10_957;
+
+/// Dates et horodatages relatifs à l'epoch Unix, comme les types Polars
 #[derive(Debug, Clone)]
 pub enum BinaryParam {
     Int4(i32),
@@ -7,7 +22,42 @@ pub enum BinaryParam {
     Bool(bool),
     Int8(i64),
     Float8(f64),
-    // ajoute d'autres types ici si besoin
+    Int2(i16),
+    Float4(f32),
+    Numeric {
+        mantissa: i128,
+        scale: u16, // valeur = mantissa * 10^-scale
+    },
+    Uuid([u8; 16]),
+    Bytea(Vec<u8>),
+    Date(i32),        // jours depuis 1970-01-01
+    Time(i64),        // microsecondes depuis minuit
+    Timestamp(i64),   // microsecondes depuis 1970-01-01, sans fuseau
+    TimestampTz(i64), // microsecondes depuis 1970-01-01 UTC
+    Interval {
+        months: i32,
+        days: i32,
+        micros: i64,
+    },
+    Json(String),
+    Jsonb(String),
+    Inet {
+        addr: IpAddr,
+        netmask: u8,
+    },
+    Array {
+        element_oid: Oid, // type des éléments, nécessaire pour un tableau vide
+        elements: Vec<Option<BinaryParam>>,
+    },
+    /// Borne absente = infinie
+    Range {
+        subtype_oid: Oid, // type des bornes
+        lower: Option<Box<BinaryParam>>,
+        upper: Option<Box<BinaryParam>>,
+        lower_inc: bool,
+        upper_inc: bool,
+        empty: bool,
+    },
 }
 
 pub fn format_params<P>(params: P) -> (Vec<Oid>, Vec<Option<Vec<u8>>>)
@@ -39,6 +89,89 @@ where
                 param_types.push(701); // OID for float8
                 param_values.push(Some(val.to_be_bytes().to_vec()));
             }
+            Some(BinaryParam::Int2(val)) => {
+                param_types.push(21); // OID for int2
+                param_values.push(Some(val.to_be_bytes().to_vec()));
+            }
+            Some(BinaryParam::Float4(val)) => {
+                param_types.push(700); // OID for float4
+                param_values.push(Some(val.to_be_bytes().to_vec()));
+            }
+            Some(BinaryParam::Numeric { mantissa, scale }) => {
+                param_types.push(1700); // OID for numeric
+                param_values.push(Some(encode_numeric(mantissa, scale)));
+            }
+            Some(BinaryParam::Uuid(val)) => {
+                param_types.push(2950); // OID for uuid
+                param_values.push(Some(val.to_vec()));
+            }
+            Some(BinaryParam::Bytea(val)) => {
+                param_types.push(17); // OID for bytea
+                param_values.push(Some(val));
+            }
+            Some(BinaryParam::Date(days)) => {
+                param_types.push(1082); // OID for date
+                param_values.push(Some((days - PG_EPOCH_DAYS).to_be_bytes().to_vec()));
+            }
+            Some(BinaryParam::Time(micros)) => {
+                param_types.push(1083); // OID for time
+                param_values.push(Some(micros.to_be_bytes().to_vec()));
+            }
+            Some(BinaryParam::Timestamp(micros)) => {
+                param_types.push(1114); // OID for timestamp
+                param_values.push(Some((micros - PG_EPOCH_MICROS).to_be_bytes().to_vec()));
+            }
+            Some(BinaryParam::TimestampTz(micros)) => {
+                param_types.push(1184); // OID for timestamptz
+                param_values.push(Some((micros - PG_EPOCH_MICROS).to_be_bytes().to_vec()));
+            }
+            Some(BinaryParam::Interval {
+                months,
+                days,
+                micros,
+            }) => {
+                param_types.push(1186); // OID for interval
+                let mut bytes = micros.to_be_bytes().to_vec();
+                bytes.extend(days.to_be_bytes());
+                bytes.extend(months.to_be_bytes());
+                param_values.push(Some(bytes));
+            }
+            Some(BinaryParam::Json(s)) => {
+                param_types.push(114); // OID for json
+                param_values.push(Some(s.into_bytes()));
+            }
+            Some(BinaryParam::Jsonb(s)) => {
+                param_types.push(3802); // OID for jsonb, version 1 puis le texte
+                let mut bytes = vec![1];
+                bytes.extend(s.into_bytes());
+                param_values.push(Some(bytes));
+            }
+            Some(BinaryParam::Inet { addr, netmask }) => {
+                param_types.push(869); // OID for inet
+                let mut buf = BytesMut::new();
+                types::inet_to_sql(addr, netmask, &mut buf);
+                param_values.push(Some(buf.to_vec()));
+            }
+            Some(BinaryParam::Array {
+                element_oid,
+                elements,
+            }) => {
+                param_types.push(array_oid(element_oid));
+                let (_, values) = format_params(elements);
+                param_values.push(Some(encode_array(element_oid, values)));
+            }
+            Some(BinaryParam::Range {
+                subtype_oid,
+                lower,
+                upper,
+                lower_inc,
+                upper_inc,
+                empty,
+            }) => {
+                param_types.push(range_oid(subtype_oid));
+                let (_, bounds) = format_params([lower.map(|b| *b), upper.map(|b| *b)]);
+                param_values.push(Some(encode_range(bounds, lower_inc, upper_inc, empty)));
+            }
             None => {
                 param_types.push(0); // unknown
                 param_values.push(None);
@@ -48,3 +181,270 @@ where
 
     (param_types, param_values)
 }
+
+/// OID du type tableau pour un type d'élément, 0 (inféré par le serveur) si inconnu
+pub fn array_oid(element_oid: Oid) -> Oid {
+    match element_oid {
+        21 => 1005,   // int2
+        23 => 1007,   // int4
+        20 => 1016,   // int8
+        25 => 1009,   // text
+        1043 => 1015, // varchar
+        16 => 1000,   // bool
+        17 => 1001,   // bytea
+        700 => 1021,  // float4
+        701 => 1022,  // float8
+        1700 => 1231, // numeric
+        2950 => 2951, // uuid
+        1082 => 1182, // date
+        1083 => 1183, // time
+        1114 => 1115, // timestamp
+        1184 => 1185, // timestamptz
+        1186 => 1187, // interval
+        114 => 199,   // json
+        3802 => 3807, // jsonb
+        869 => 1041,  // inet
+        _ => 0,
+    }
+}
+
+/// OID du range pour un type de bornes, 0 (inféré par le serveur) si inconnu
+pub fn range_oid(subtype_oid: Oid) -> Oid {
+    match subtype_oid {
+        23 => 3904,   // int4range
+        20 => 3926,   // int8range
+        1700 => 3906, // numrange
+        1114 => 3908, // tsrange
+        1184 => 3910, // tstzrange
+        1082 => 3912, // daterange
+        _ => 0,
+    }
+}
+
+/// Range au format binaire : drapeaux puis longueur et valeur de chaque borne finie
+fn encode_range(
+    bounds: Vec<Option<Vec<u8>>>,
+    lower_inc: bool,
+    upper_inc: bool,
+    empty: bool,
+) -> Vec<u8> {
+    if empty {
+        return vec![0x01];
+    }
+    let mut flags = 0u8;
+    match bounds[0] {
+        None => flags |= 0x08,
+        Some(_) if lower_inc => flags |= 0x02,
+        Some(_) => {}
+    }
+    match bounds[1] {
+        None => flags |= 0x10,
+        Some(_) if upper_inc => flags |= 0x04,
+        Some(_) => {}
+    }
+    let mut bytes = vec![flags];
+    for bound in bounds.into_iter().flatten() {
+        bytes.extend((bound.len() as i32).to_be_bytes());
+        bytes.extend(bound);
+    }
+    bytes
+}
+
+/// Tableau à une dimension au format binaire : en-tête puis longueur et valeur de chaque élément
+fn encode_array(element_oid: Oid, values: Vec<Option<Vec<u8>>>) -> Vec<u8> {
+    let has_null = values.iter().any(Option::is_none);
+    let ndim: i32 = if values.is_empty() { 0 } else { 1 };
+
+    let mut bytes = Vec::new();
+    bytes.extend(ndim.to_be_bytes());
+    bytes.extend((has_null as i32).to_be_bytes());
+    bytes.extend(element_oid.to_be_bytes());
+    if ndim == 1 {
+        bytes.extend((values.len() as i32).to_be_bytes());
+        bytes.extend(1i32.to_be_bytes()); // borne inférieure
+    }
+    for value in values {
+        match value {
+            Some(value) => {
+                bytes.extend((value.len() as i32).to_be_bytes());
+                bytes.extend(value);
+            }
+            None => bytes.extend((-1i32).to_be_bytes()),
+        }
+    }
+    bytes
+}
+
+impl BinaryParam {
+    /// OID du type PostgreSQL correspondant à l'encodage de la valeur
+    pub fn type_oid(&self) -> Oid {
+        match self {
+            BinaryParam::Int4(_) => 23,
+            BinaryParam::Text(_) => 25,
+            BinaryParam::Bool(_) => 16,
+            BinaryParam::Int8(_) => 20,
+            BinaryParam::Float8(_) => 701,
+            BinaryParam::Int2(_) => 21,
+            BinaryParam::Float4(_) => 700,
+            BinaryParam::Numeric { .. } => 1700,
+            BinaryParam::Uuid(_) => 2950,
+            BinaryParam::Bytea(_) => 17,
+            BinaryParam::Date(_) => 1082,
+            BinaryParam::Time(_) => 1083,
+            BinaryParam::Timestamp(_) => 1114,
+            BinaryParam::TimestampTz(_) => 1184,
+            BinaryParam::Interval { .. } => 1186,
+            BinaryParam::Json(_) => 114,
+            BinaryParam::Jsonb(_) => 3802,
+            BinaryParam::Inet { .. } => 869,
+            BinaryParam::Array { element_oid, .. } => array_oid(*element_oid),
+            BinaryParam::Range { subtype_oid, .. } => range_oid(*subtype_oid),
+        }
+    }
+}
+
+/// Convertit les valeurs vers les types des paramètres décrits par le serveur
+pub fn coerce_params(
+    params: Vec<Option<BinaryParam>>,
+    server_types: &[Oid],
+) -> PgToPlResult<Vec<Option<BinaryParam>>> {
+    if params.len() != server_types.len() {
+        return Err(PgToPlError::ParamCountMismatch(
+            server_types.len(),
+            params.len(),
+        ));
+    }
+
+    params
+        .into_iter()
+        .zip(server_types)
+        .map(|(param, oid)| param.map(|param| coerce_param(param, *oid)).transpose())
+        .collect()
+}
+
+/// Format d'envoi d'un paramètre : texte pour `BinaryParam::Text`, binaire sinon
+pub fn param_format(param: &Option<BinaryParam>) -> i16 {
+    match param {
+        Some(BinaryParam::Text(_)) => 0,
+        _ => 1,
+    }
+}
+
+/// Vrai si une chaîne vers `oid` doit passer par la fonction d'entrée texte du type
+/// (uuid, inet, intervalles...) faute d'encodage binaire commun avec text
+pub(crate) fn needs_text_input(oid: Oid) -> bool {
+    is_builtin_oid(oid) && !matches!(oid, 25 | 1043 | 1042 | 19 | 18 | 142 | 705 | 114 | 3802)
+}
+
+/// Une chaîne vers un type sans encodage binaire commun reste du texte, envoyé au format
+/// texte (voir `param_format`)
+pub(crate) fn coerce_param(param: BinaryParam, oid: Oid) -> PgToPlResult<BinaryParam> {
+    if matches!(param, BinaryParam::Text(_)) && needs_text_input(oid) {
+        return Ok(param);
+    }
+    coerce_binary(param, oid)
+}
+
+fn coerce_binary(param: BinaryParam, oid: Oid) -> PgToPlResult<BinaryParam> {
+    let from = param.type_oid();
+    // types hors catalogue statique (enum, domaine, extension) : validés par le serveur
+    if from == oid || !is_builtin_oid(oid) {
+        return Ok(param);
+    }
+    let mismatch = || PgToPlError::ParamCoercion(from, oid);
+
+    let coerced = match (param, oid) {
+        (BinaryParam::Int2(val), _) => coerce_integer(val as i64, oid).ok_or_else(mismatch)?,
+        (BinaryParam::Int4(val), _) => coerce_integer(val as i64, oid).ok_or_else(mismatch)?,
+        (BinaryParam::Int8(val), _) => coerce_integer(val, oid).ok_or_else(mismatch)?,
+        (BinaryParam::Float4(val), 701) => BinaryParam::Float8(val as f64),
+        // refusé si la valeur ne survit pas au passage en f32
+        (BinaryParam::Float8(val), 700) if val.is_nan() || (val as f32) as f64 == val => {
+            BinaryParam::Float4(val as f32)
+        }
+        (BinaryParam::Float4(val), 1700) => {
+            float_to_numeric(val.to_string()).ok_or_else(mismatch)?
+        }
+        (BinaryParam::Float8(val), 1700) => {
+            float_to_numeric(val.to_string()).ok_or_else(mismatch)?
+        }
+        // même encodage binaire que text
+        (param @ BinaryParam::Text(_), 1043 | 1042 | 19 | 18 | 142 | 705) => param,
+        (BinaryParam::Text(val) | BinaryParam::Json(val), 3802) => BinaryParam::Jsonb(val),
+        (BinaryParam::Text(val) | BinaryParam::Jsonb(val), 114) => BinaryParam::Json(val),
+        (BinaryParam::Json(val) | BinaryParam::Jsonb(val), 25) => BinaryParam::Text(val),
+        (BinaryParam::Timestamp(val), 1184) => BinaryParam::TimestampTz(val),
+        (BinaryParam::TimestampTz(val), 1114) => BinaryParam::Timestamp(val),
+        (BinaryParam::Date(days), 1114) => BinaryParam::Timestamp(days as i64 * 86_400_000_000),
+        (BinaryParam::Date(days), 1184) => BinaryParam::TimestampTz(days as i64 * 86_400_000_000),
+        (BinaryParam::Inet { addr, netmask }, 650) => BinaryParam::Inet { addr, netmask },
+        (BinaryParam::Array { elements, .. }, _) => {
+            let element_oid = array_element_oid(oid).ok_or_else(mismatch)?;
+            let elements = elements
+                .into_iter()
+                .map(|element| element.map(|e| coerce_binary(e, element_oid)).transpose())
+                .collect::<PgToPlResult<_>>()?;
+            BinaryParam::Array {
+                element_oid,
+                elements,
+            }
+        }
+        (
+            BinaryParam::Range {
+                lower,
+                upper,
+                lower_inc,
+                upper_inc,
+                empty,
+                ..
+            },
+            _,
+        ) => {
+            let subtype_oid = range_subtype_oid(oid).ok_or_else(mismatch)?;
+            let bound = |bound: Option<Box<BinaryParam>>| {
+                bound
+                    .map(|b| coerce_binary(*b, subtype_oid).map(Box::new))
+                    .transpose()
+            };
+            BinaryParam::Range {
+                subtype_oid,
+                lower: bound(lower)?,
+                upper: bound(upper)?,
+                lower_inc,
+                upper_inc,
+                empty,
+            }
+        }
+        _ => return Err(mismatch()),
+    };
+    Ok(coerced)
+}
+
+/// Entier vers un autre type numérique, `None` si hors limites, non représentable exactement
+/// en flottant ou type non numérique
+fn coerce_integer(val: i64, oid: Oid) -> Option<BinaryParam> {
+    let param = match oid {
+        21 => BinaryParam::Int2(i16::try_from(val).ok()?),
+        23 => BinaryParam::Int4(i32::try_from(val).ok()?),
+        20 => BinaryParam::Int8(val),
+        700 if (val as f32) as i128 == val as i128 => BinaryParam::Float4(val as f32),
+        701 if (val as f64) as i128 == val as i128 => BinaryParam::Float8(val as f64),
+        1700 => BinaryParam::Numeric {
+            mantissa: val as i128,
+            scale: 0,
+        },
+        _ => return None,
+    };
+    Some(param)
+}
+
+/// Écriture décimale d'un flottant (la plus courte qui le relit à l'identique) en numeric,
+/// `None` pour NaN, ±Infinity ou plus de 38 chiffres
+fn float_to_numeric(text: String) -> Option<BinaryParam> {
+    let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+    let mantissa: i128 = format!("{}{}", integer, fraction).parse().ok()?;
+    Some(BinaryParam::Numeric {
+        mantissa,
+        scale: fraction.len() as u16,
+    })
+}
@@ -0,0 +1,632 @@
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+
+use polars::prelude::{Column, DataType, Field, Series, TimeUnit};
+use postgres_protocol::Oid;
+
+use crate::PgToPlError;
+use crate::models::params::{BinaryParam, array_oid, range_oid};
+use crate::utils::error::PgToPlResult;
+
+/// Conversion d'une valeur Rust en paramètre de requête
+pub trait ToParam {
+    /// Valeur encodée, `None` = NULL
+    fn to_param(&self) -> PgToPlResult<Option<BinaryParam>>;
+
+    /// OID PostgreSQL du type, utilisé comme type d'élément des tableaux (0 = inféré)
+    fn param_oid() -> Oid
+    where
+        Self: Sized;
+
+    /// Encodage d'une tranche entière à la place d'un tableau d'éléments (`[u8]` en bytea)
+    fn slice_param(_values: &[Self]) -> Option<BinaryParam>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+/// Liste de paramètres acceptée par `Client::query_params`
+pub trait IntoParams {
+    fn into_params(self) -> PgToPlResult<Vec<Option<BinaryParam>>>;
+}
+
+impl IntoParams for Vec<Option<BinaryParam>> {
+    fn into_params(self) -> PgToPlResult<Vec<Option<BinaryParam>>> {
+        Ok(self)
+    }
+}
+
+impl IntoParams for &[&dyn ToParam] {
+    fn into_params(self) -> PgToPlResult<Vec<Option<BinaryParam>>> {
+        self.iter().map(|param| param.to_param()).collect()
+    }
+}
+
+impl<const N: usize> IntoParams for [&dyn ToParam; N] {
+    fn into_params(self) -> PgToPlResult<Vec<Option<BinaryParam>>> {
+        self.iter().map(|param| param.to_param()).collect()
+    }
+}
+
+impl<const N: usize> IntoParams for &[&dyn ToParam; N] {
+    fn into_params(self) -> PgToPlResult<Vec<Option<BinaryParam>>> {
+        self.iter().map(|param| param.to_param()).collect()
+    }
+}
+
+impl IntoParams for () {
+    fn into_params(self) -> PgToPlResult<Vec<Option<BinaryParam>>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Source de paramètres nommés acceptée par `Client::query_named`.
+/// Une structure peut l'implémenter en associant chaque nom à un champ.
+pub trait NamedParams {
+    /// Valeur du paramètre `name`, `None` si le nom est inconnu
+    fn named_param(&self, name: &str) -> Option<&dyn ToParam>;
+}
+
+impl<T: ToParam> NamedParams for HashMap<String, T> {
+    fn named_param(&self, name: &str) -> Option<&dyn ToParam> {
+        self.get(name).map(|value| value as &dyn ToParam)
+    }
+}
+
+impl<T: ToParam> NamedParams for HashMap<&str, T> {
+    fn named_param(&self, name: &str) -> Option<&dyn ToParam> {
+        self.get(name).map(|value| value as &dyn ToParam)
+    }
+}
+
+impl<T: ToParam> NamedParams for BTreeMap<String, T> {
+    fn named_param(&self, name: &str) -> Option<&dyn ToParam> {
+        self.get(name).map(|value| value as &dyn ToParam)
+    }
+}
+
+impl<T: ToParam> NamedParams for BTreeMap<&str, T> {
+    fn named_param(&self, name: &str) -> Option<&dyn ToParam> {
+        self.get(name).map(|value| value as &dyn ToParam)
+    }
+}
+
+impl NamedParams for [(&str, &dyn ToParam)] {
+    fn named_param(&self, name: &str) -> Option<&dyn ToParam> {
+        self.iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+    }
+}
+
+impl<const N: usize> NamedParams for [(&str, &dyn ToParam); N] {
+    fn named_param(&self, name: &str) -> Option<&dyn ToParam> {
+        self.as_slice().named_param(name)
+    }
+}
+
+impl<T: NamedParams + ?Sized> NamedParams for &T {
+    fn named_param(&self, name: &str) -> Option<&dyn ToParam> {
+        (**self).named_param(name)
+    }
+}
+
+macro_rules! tuple_into_params {
+    ($($name:ident),+) => {
+        impl<$($name: ToParam),+> IntoParams for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn into_params(self) -> PgToPlResult<Vec<Option<BinaryParam>>> {
+                let ($($name,)+) = self;
+                Ok(vec![$($name.to_param()?),+])
+            }
+        }
+    };
+}
+
+tuple_into_params!(A);
+tuple_into_params!(A, B);
+tuple_into_params!(A, B, C);
+tuple_into_params!(A, B, C, D);
+tuple_into_params!(A, B, C, D, E);
+tuple_into_params!(A, B, C, D, E, F);
+tuple_into_params!(A, B, C, D, E, F, G);
+tuple_into_params!(A, B, C, D, E, F, G, H);
+tuple_into_params!(A, B, C, D, E, F, G, H, I);
+tuple_into_params!(A, B, C, D, E, F, G, H, I, J);
+tuple_into_params!(A, B, C, D, E, F, G, H, I, J, K);
+tuple_into_params!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+macro_rules! to_param {
+    ($type:ty, $oid:expr, |$val:ident| $param:expr) => {
+        impl ToParam for $type {
+            fn to_param(&self) -> $crate::utils::error::PgToPlResult<Option<BinaryParam>> {
+                let $val = self;
+                Ok(Some($param))
+            }
+
+            fn param_oid() -> postgres_protocol::Oid {
+                $oid
+            }
+        }
+    };
+}
+
+to_param!(bool, 16, |val| BinaryParam::Bool(*val));
+to_param!(i8, 21, |val| BinaryParam::Int2(*val as i16));
+to_param!(i16, 21, |val| BinaryParam::Int2(*val));
+to_param!(i32, 23, |val| BinaryParam::Int4(*val));
+to_param!(i64, 20, |val| BinaryParam::Int8(*val));
+to_param!(u16, 23, |val| BinaryParam::Int4(*val as i32));
+to_param!(u32, 20, |val| BinaryParam::Int8(*val as i64));
+// au-delà de i64::MAX : numeric plutôt qu'un int8, comme pour une Series UInt64
+to_param!(u64, 1700, |val| BinaryParam::Numeric {
+    mantissa: *val as i128,
+    scale: 0,
+});
+to_param!(f32, 700, |val| BinaryParam::Float4(*val));
+to_param!(f64, 701, |val| BinaryParam::Float8(*val));
+to_param!(String, 25, |val| BinaryParam::Text(val.clone()));
+to_param!(&str, 25, |val| BinaryParam::Text(val.to_string()));
+to_param!(IpAddr, 869, |val| BinaryParam::Inet {
+    addr: *val,
+    netmask: if val.is_ipv4() { 32 } else { 128 },
+});
+
+/// `u8` seul en smallint, `Vec<u8>` et `&[u8]` en bytea
+impl ToParam for u8 {
+    fn to_param(&self) -> PgToPlResult<Option<BinaryParam>> {
+        Ok(Some(BinaryParam::Int2(*self as i16)))
+    }
+
+    fn param_oid() -> Oid {
+        21
+    }
+
+    fn slice_param(values: &[Self]) -> Option<BinaryParam> {
+        Some(BinaryParam::Bytea(values.to_vec()))
+    }
+}
+
+/// Type connu seulement à l'exécution : un tableau prend le type de son premier élément non NULL
+impl ToParam for BinaryParam {
+    fn to_param(&self) -> PgToPlResult<Option<BinaryParam>> {
+        Ok(Some(self.clone()))
+    }
+
+    fn param_oid() -> Oid {
+        0
+    }
+}
+
+impl<T: ToParam> ToParam for Option<T> {
+    fn to_param(&self) -> PgToPlResult<Option<BinaryParam>> {
+        match self {
+            Some(value) => value.to_param(),
+            None => Ok(None),
+        }
+    }
+
+    fn param_oid() -> Oid {
+        T::param_oid()
+    }
+}
+
+impl<T: ToParam> ToParam for &T {
+    fn to_param(&self) -> PgToPlResult<Option<BinaryParam>> {
+        (**self).to_param()
+    }
+
+    fn param_oid() -> Oid {
+        T::param_oid()
+    }
+}
+
+impl ToParam for &dyn ToParam {
+    fn to_param(&self) -> PgToPlResult<Option<BinaryParam>> {
+        (**self).to_param()
+    }
+
+    fn param_oid() -> Oid {
+        0
+    }
+}
+
+/// Tableau à une dimension (`Vec<u8>` est encodé en bytea)
+impl<T: ToParam> ToParam for Vec<T> {
+    fn to_param(&self) -> PgToPlResult<Option<BinaryParam>> {
+        array_param(self).map(Some)
+    }
+
+    fn param_oid() -> Oid {
+        0
+    }
+}
+
+/// Sans OID statique (`BinaryParam`, `&dyn ToParam`), celui du premier élément non NULL
+fn array_param<T: ToParam>(values: &[T]) -> PgToPlResult<BinaryParam> {
+    if let Some(param) = T::slice_param(values) {
+        return Ok(param);
+    }
+    let elements = values
+        .iter()
+        .map(ToParam::to_param)
+        .collect::<PgToPlResult<Vec<_>>>()?;
+    let element_oid = match T::param_oid() {
+        0 => elements
+            .iter()
+            .flatten()
+            .next()
+            .map_or(0, BinaryParam::type_oid),
+        oid => oid,
+    };
+    Ok(BinaryParam::Array {
+        element_oid,
+        elements,
+    })
+}
+
+#[cfg(feature = "uuid")]
+to_param!(uuid::Uuid, 2950, |val| BinaryParam::Uuid(*val.as_bytes()));
+
+#[cfg(feature = "chrono")]
+mod chrono_params {
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike};
+    use postgres_protocol::Oid;
+
+    use super::ToParam;
+    use crate::models::params::BinaryParam;
+    use crate::utils::error::PgToPlResult;
+
+    to_param!(NaiveDate, 1082, |val| BinaryParam::Date(
+        (*val - NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()).num_days() as i32
+    ));
+    to_param!(NaiveTime, 1083, |val| BinaryParam::Time(
+        val.num_seconds_from_midnight() as i64 * 1_000_000 + val.nanosecond() as i64 / 1000
+    ));
+    to_param!(NaiveDateTime, 1114, |val| BinaryParam::Timestamp(
+        val.and_utc().timestamp_micros()
+    ));
+
+    impl<Tz: TimeZone> ToParam for DateTime<Tz> {
+        fn to_param(&self) -> PgToPlResult<Option<BinaryParam>> {
+            Ok(Some(BinaryParam::TimestampTz(self.timestamp_micros())))
+        }
+
+        fn param_oid() -> Oid {
+            1184
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_params {
+    use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+    use super::ToParam;
+    use crate::models::params::BinaryParam;
+
+    to_param!(Date, 1082, |val| BinaryParam::Date(
+        val.to_julian_day() - Date::from_ordinal_date(1970, 1).unwrap().to_julian_day()
+    ));
+    to_param!(Time, 1083, |val| BinaryParam::Time(
+        (*val - Time::MIDNIGHT).whole_microseconds() as i64
+    ));
+    to_param!(PrimitiveDateTime, 1114, |val| BinaryParam::Timestamp(
+        (val.assume_utc().unix_timestamp_nanos() / 1000) as i64
+    ));
+    to_param!(OffsetDateTime, 1184, |val| BinaryParam::TimestampTz(
+        (val.unix_timestamp_nanos() / 1000) as i64
+    ));
+}
+
+/// Tranche : tableau à une dimension, comme `Vec<T>`
+impl<T: ToParam> ToParam for &[T] {
+    fn to_param(&self) -> PgToPlResult<Option<BinaryParam>> {
+        array_param(self).map(Some)
+    }
+
+    fn param_oid() -> Oid {
+        0
+    }
+}
+
+/// Series Polars : tableau à une dimension, pour `WHERE id = ANY($1)`
+impl ToParam for Series {
+    fn to_param(&self) -> PgToPlResult<Option<BinaryParam>> {
+        series_to_array(self).map(Some)
+    }
+
+    fn param_oid() -> Oid {
+        0
+    }
+}
+
+impl ToParam for Column {
+    fn to_param(&self) -> PgToPlResult<Option<BinaryParam>> {
+        series_to_array(self.as_materialized_series()).map(Some)
+    }
+
+    fn param_oid() -> Oid {
+        0
+    }
+}
+
+fn series_to_array(series: &Series) -> PgToPlResult<BinaryParam> {
+    let (element_oid, elements) = series_to_params(series)?;
+    Ok(BinaryParam::Array {
+        element_oid,
+        elements,
+    })
+}
+
+/// Une valeur par ligne, avec l'OID du type choisi.
+/// Les chaînes et catégories sont envoyées en texte, les structs de la forme des ranges
+/// décodés en range ; les autres types sans équivalent sont refusés.
+pub(crate) fn series_to_params(series: &Series) -> PgToPlResult<(Oid, Vec<Option<BinaryParam>>)> {
+    let cast = |dtype: &DataType| series.strict_cast(dtype);
+
+    let params = match series.dtype() {
+        DataType::Boolean => (16, collect(series.bool()?, BinaryParam::Bool)),
+        DataType::Int8 | DataType::Int16 | DataType::UInt8 => (
+            21,
+            collect(cast(&DataType::Int16)?.i16()?, BinaryParam::Int2),
+        ),
+        DataType::Int32 | DataType::UInt16 => (
+            23,
+            collect(cast(&DataType::Int32)?.i32()?, BinaryParam::Int4),
+        ),
+        DataType::Int64 | DataType::UInt32 => (
+            20,
+            collect(cast(&DataType::Int64)?.i64()?, BinaryParam::Int8),
+        ),
+        // au-delà de i64::MAX : numeric plutôt qu'un int8
+        DataType::UInt64 => (
+            1700,
+            collect(series.u64()?, |val| BinaryParam::Numeric {
+                mantissa: i128::from(val),
+                scale: 0,
+            }),
+        ),
+        DataType::Float32 => (700, collect(series.f32()?, BinaryParam::Float4)),
+        DataType::Float64 => (701, collect(series.f64()?, BinaryParam::Float8)),
+        DataType::Binary => (
+            17,
+            series
+                .binary()?
+                .into_iter()
+                .map(|val| val.map(|bytes| BinaryParam::Bytea(bytes.to_vec())))
+                .collect(),
+        ),
+        DataType::Date => (
+            1082,
+            collect(cast(&DataType::Int32)?.i32()?, BinaryParam::Date),
+        ),
+        DataType::Time => {
+            let nanos = cast(&DataType::Int64)?;
+            let micros = nanos.i64()?.into_iter();
+            (
+                1083,
+                micros
+                    .map(|val| val.map(|nanos| BinaryParam::Time(nanos / 1000)))
+                    .collect(),
+            )
+        }
+        DataType::Datetime(_, tz) => {
+            let micros = cast(&DataType::Datetime(TimeUnit::Microseconds, tz.clone()))?;
+            let micros = micros.strict_cast(&DataType::Int64)?;
+            match tz {
+                Some(_) => (1184, collect(micros.i64()?, BinaryParam::TimestampTz)),
+                None => (1114, collect(micros.i64()?, BinaryParam::Timestamp)),
+            }
+        }
+        DataType::Decimal(_, scale) => {
+            let scale = scale.unwrap_or(0) as u16;
+            let values = series.decimal()?.physical().clone();
+            (
+                1700,
+                values
+                    .into_iter()
+                    .map(|val| val.map(|mantissa| BinaryParam::Numeric { mantissa, scale }))
+                    .collect(),
+            )
+        }
+        DataType::List(inner) => {
+            let (inner_oid, _) =
+                series_to_params(&Series::new_empty(series.name().clone(), inner))?;
+            (
+                array_oid(inner_oid),
+                series
+                    .list()?
+                    .into_iter()
+                    .map(|val| val.map(|values| series_to_array(&values)).transpose())
+                    .collect::<PgToPlResult<_>>()?,
+            )
+        }
+        DataType::String | DataType::Categorical(_, _) | DataType::Enum(_, _) => {
+            let texts = cast(&DataType::String)?;
+            (
+                25,
+                texts
+                    .str()?
+                    .into_iter()
+                    .map(|val| val.map(|text| BinaryParam::Text(text.to_string())))
+                    .collect(),
+            )
+        }
+        DataType::Struct(fields) if is_range_struct(fields) => series_to_ranges(series)?,
+        // objet JSON, comme la colonne jsonb de `create_table_from_schema`
+        DataType::Struct(_) => (
+            3802,
+            series_to_json(series)?
+                .into_iter()
+                .map(|val| val.map(BinaryParam::Jsonb))
+                .collect(),
+        ),
+        DataType::Duration(unit) => {
+            let factor = |nanos: i64| match unit {
+                TimeUnit::Nanoseconds => nanos / 1000,
+                TimeUnit::Microseconds => nanos,
+                TimeUnit::Milliseconds => nanos * 1000,
+            };
+            let values = series.to_physical_repr();
+            (
+                1186,
+                values
+                    .i64()?
+                    .into_iter()
+                    .map(|val| {
+                        val.map(|val| BinaryParam::Interval {
+                            months: 0,
+                            days: 0,
+                            micros: factor(val),
+                        })
+                    })
+                    .collect(),
+            )
+        }
+        DataType::Null => (25, vec![None; series.len()]),
+        dtype => return Err(PgToPlError::UnsupportedDataType(dtype.to_string())),
+    };
+    Ok(params)
+}
+
+/// Texte JSON de chaque ligne, `None` pour NULL. Les structures deviennent des objets,
+/// les listes des tableaux ; les flottants non finis sont écrits `null`, les autres types
+/// scalaires en chaîne.
+fn series_to_json(series: &Series) -> PgToPlResult<Vec<Option<String>>> {
+    let values = match series.dtype() {
+        DataType::Boolean => series
+            .bool()?
+            .into_iter()
+            .map(|val| val.map(|val| val.to_string()))
+            .collect(),
+        DataType::Float32 | DataType::Float64 => series
+            .cast(&DataType::Float64)?
+            .f64()?
+            .into_iter()
+            .map(|val| val.filter(|val| val.is_finite()).map(|val| val.to_string()))
+            .collect(),
+        dtype if dtype.is_integer() => series
+            .cast(&DataType::String)?
+            .str()?
+            .into_iter()
+            .map(|val| val.map(String::from))
+            .collect(),
+        DataType::Struct(fields) => {
+            let parts = series.struct_()?.fields_as_series();
+            let members = parts
+                .iter()
+                .map(series_to_json)
+                .collect::<PgToPlResult<Vec<_>>>()?;
+            let keys: Vec<String> = fields.iter().map(|f| json_string(f.name())).collect();
+            series
+                .is_not_null()
+                .into_iter()
+                .enumerate()
+                .map(|(row, valid)| {
+                    valid.unwrap_or(false).then(|| {
+                        let entries: Vec<String> = keys
+                            .iter()
+                            .zip(&members)
+                            .map(|(key, values)| {
+                                let value = values[row].as_deref().unwrap_or("null");
+                                format!("{}:{}", key, value)
+                            })
+                            .collect();
+                        format!("{{{}}}", entries.join(","))
+                    })
+                })
+                .collect()
+        }
+        DataType::List(_) => series
+            .list()?
+            .into_iter()
+            .map(|val| {
+                val.map(|items| {
+                    let items: Vec<String> = series_to_json(&items)?
+                        .into_iter()
+                        .map(|item| item.unwrap_or_else(|| String::from("null")))
+                        .collect();
+                    Ok(format!("[{}]", items.join(",")))
+                })
+                .transpose()
+            })
+            .collect::<PgToPlResult<_>>()?,
+        DataType::Null => vec![None; series.len()],
+        dtype => series
+            .cast(&DataType::String)
+            .map_err(|_| PgToPlError::UnsupportedDataType(dtype.to_string()))?
+            .str()?
+            .into_iter()
+            .map(|val| val.map(json_string))
+            .collect(),
+    };
+    Ok(values)
+}
+
+/// Chaîne JSON entre guillemets, caractères de contrôle échappés
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Struct {lower, upper, lower_inc, upper_inc, empty}, forme des ranges décodés
+fn is_range_struct(fields: &[Field]) -> bool {
+    let names = fields.iter().map(|field| field.name().as_str());
+    names.eq(["lower", "upper", "lower_inc", "upper_inc", "empty"])
+}
+
+fn series_to_ranges(series: &Series) -> PgToPlResult<(Oid, Vec<Option<BinaryParam>>)> {
+    let parts = series.struct_()?.fields_as_series();
+    let (subtype_oid, lower) = series_to_params(&parts[0])?;
+    let (_, upper) = series_to_params(&parts[1])?;
+    let oid = range_oid(subtype_oid);
+    if oid == 0 {
+        return Err(PgToPlError::UnsupportedDataType(series.dtype().to_string()));
+    }
+    let flag = |i: usize| -> PgToPlResult<Vec<bool>> {
+        Ok(parts[i]
+            .bool()?
+            .into_iter()
+            .map(|val| val.unwrap_or(false))
+            .collect())
+    };
+    let (lower_inc, upper_inc, empty) = (flag(2)?, flag(3)?, flag(4)?);
+
+    let ranges = series
+        .is_not_null()
+        .into_iter()
+        .zip(lower.into_iter().zip(upper))
+        .enumerate()
+        .map(|(i, (valid, (lower, upper)))| {
+            valid.unwrap_or(false).then(|| BinaryParam::Range {
+                subtype_oid,
+                lower: lower.map(Box::new),
+                upper: upper.map(Box::new),
+                lower_inc: lower_inc[i],
+                upper_inc: upper_inc[i],
+                empty: empty[i],
+            })
+        })
+        .collect();
+    Ok((oid, ranges))
+}
+
+fn collect<T, I>(values: I, param: fn(T) -> BinaryParam) -> Vec<Option<BinaryParam>>
+where
+    I: IntoIterator<Item = Option<T>>,
+{
+    values.into_iter().map(|val| val.map(param)).collect()
+}
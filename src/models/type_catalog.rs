@@ -0,0 +1,326 @@
+use std::collections::{HashMap, HashSet};
+
+use postgres_protocol::Oid;
+use tokio::net::TcpStream;
+
+use crate::models::client::simple_query;
+use crate::models::column_result::is_builtin_oid;
+use crate::{PgToPlError, utils::error::PgToPlResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeKind {
+    Base,
+    Composite,
+    Domain,
+    Enum,
+    Pseudo,
+    Range,
+    Multirange,
+}
+
+/// Type d'extension décodé nativement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionType {
+    Vector,
+    HalfVec,
+    SparseVec,
+    Geometry, // geometry et geography
+    Hstore,
+}
+
+/// Types d'extension décodés nativement : (extension, type), recherchés à la connexion parmi les
+/// types appartenant à l'extension, quel que soit leur schéma
+pub const EXTENSION_TYPES: &[(&str, &str, ExtensionType)] = &[
+    ("vector", "vector", ExtensionType::Vector),
+    ("vector", "halfvec", ExtensionType::HalfVec),
+    ("vector", "sparsevec", ExtensionType::SparseVec),
+    ("postgis", "geometry", ExtensionType::Geometry),
+    ("postgis", "geography", ExtensionType::Geometry),
+    ("hstore", "hstore", ExtensionType::Hstore),
+];
+
+/// Entrée de `pg_type` pour un type non connu statiquement
+#[derive(Debug, Clone)]
+pub struct PgType {
+    pub oid: Oid,
+    pub name: String,
+    pub kind: TypeKind,
+    pub category: char,
+    pub element: Oid,
+    pub relid: Oid,
+    pub subtype: Oid, // range : type des bornes, multirange : type du range
+    pub base: Oid,    // domaine : type de base (typbasetype)
+    pub fields: Vec<(String, Oid)>, // attributs d'un type composite
+    pub enum_labels: Vec<String>, // dans l'ordre de tri PostgreSQL (enumsortorder)
+}
+
+impl PgType {
+    /// OID de l'élément si le type est un tableau
+    pub fn array_element(&self) -> Option<Oid> {
+        (self.category == 'A' && self.element != 0).then_some(self.element)
+    }
+}
+
+/// Cache des types lus dans le catalogue, propre à chaque connexion
+#[derive(Debug, Default)]
+pub struct TypeCatalog {
+    types: HashMap<Oid, PgType>,
+    extensions: HashMap<Oid, ExtensionType>,
+    money_scale: Option<usize>, // décimales de `money`, selon `lc_monetary` à la connexion
+}
+
+impl TypeCatalog {
+    pub fn get(&self, oid: Oid) -> Option<&PgType> {
+        self.types.get(&oid)
+    }
+
+    /// Type d'extension décodé nativement, d'après son OID
+    pub fn extension(&self, oid: Oid) -> Option<ExtensionType> {
+        self.extensions.get(&oid).copied()
+    }
+
+    /// Décimales de `money` lues à la connexion, 2 à défaut
+    pub fn money_scale(&self) -> usize {
+        self.money_scale.unwrap_or(2)
+    }
+
+    /// Lit le nombre de décimales de `money`, qui dépend de `lc_monetary`
+    pub async fn load_money_scale(&mut self, stream: &mut TcpStream) -> PgToPlResult<()> {
+        let rows = simple_query(stream, "SELECT scale(1::money::numeric)").await?;
+        self.money_scale = rows
+            .first()
+            .and_then(|row| row[0].as_deref())
+            .and_then(|scale| scale.parse().ok());
+        Ok(())
+    }
+
+    /// Charge depuis le catalogue les types inconnus, ainsi que les types qu'ils référencent
+    pub async fn resolve<I>(&mut self, stream: &mut TcpStream, oids: I) -> PgToPlResult<()>
+    where
+        I: IntoIterator<Item = Oid>,
+    {
+        let mut pending: HashSet<Oid> = oids
+            .into_iter()
+            .filter(|oid| !is_builtin_oid(*oid) && !self.types.contains_key(oid))
+            .collect();
+
+        while !pending.is_empty() {
+            let oid_list = join_oids(&pending);
+            pending.clear();
+
+            let rows = simple_query(
+                stream,
+                &format!(
+                    "SELECT oid, typname, typtype, typcategory, typelem, typrelid, typbasetype \
+                     FROM pg_catalog.pg_type WHERE oid IN ({})",
+                    oid_list
+                ),
+            )
+            .await?;
+
+            let mut enums = Vec::new();
+            let mut composites = Vec::new();
+            let mut ranges = Vec::new();
+            let mut multiranges = Vec::new();
+            for row in rows {
+                let pg_type = PgType {
+                    oid: parse_oid(&row[0])?,
+                    name: row[1].clone().unwrap_or_default(),
+                    kind: parse_kind(&row[2]),
+                    category: row[3]
+                        .as_deref()
+                        .and_then(|c| c.chars().next())
+                        .unwrap_or(' '),
+                    element: parse_oid(&row[4])?,
+                    relid: parse_oid(&row[5])?,
+                    subtype: 0,
+                    base: parse_oid(&row[6])?,
+                    fields: Vec::new(),
+                    enum_labels: Vec::new(),
+                };
+
+                match pg_type.kind {
+                    TypeKind::Enum => enums.push(pg_type.oid),
+                    TypeKind::Composite => composites.push(pg_type.relid),
+                    TypeKind::Range => ranges.push(pg_type.oid),
+                    TypeKind::Multirange => multiranges.push(pg_type.oid),
+                    TypeKind::Domain => {
+                        pending.insert(pg_type.base);
+                    }
+                    _ => {}
+                }
+                if let Some(element) = pg_type.array_element() {
+                    pending.insert(element);
+                }
+                self.types.insert(pg_type.oid, pg_type);
+            }
+
+            if !enums.is_empty() {
+                self.load_enum_labels(stream, &enums).await?;
+            }
+            if !composites.is_empty() {
+                let field_types = self.load_composite_fields(stream, &composites).await?;
+                pending.extend(field_types);
+            }
+            if !ranges.is_empty() {
+                let query = format!(
+                    "SELECT rngtypid, rngsubtype FROM pg_catalog.pg_range WHERE rngtypid IN ({})",
+                    join_oids(&ranges)
+                );
+                pending.extend(self.load_subtypes(stream, &query).await?);
+            }
+            if !multiranges.is_empty() {
+                let query = format!(
+                    "SELECT rngmultitypid, rngtypid FROM pg_catalog.pg_range \
+                     WHERE rngmultitypid IN ({})",
+                    join_oids(&multiranges)
+                );
+                pending.extend(self.load_subtypes(stream, &query).await?);
+            }
+
+            pending.retain(|oid| !is_builtin_oid(*oid) && !self.types.contains_key(oid));
+        }
+
+        Ok(())
+    }
+
+    /// Recherche les types de `EXTENSION_TYPES` parmi les membres des extensions installées ;
+    /// un type homonyme créé hors de l'extension n'est pas concerné
+    pub async fn load_extension_types(&mut self, stream: &mut TcpStream) -> PgToPlResult<()> {
+        let rows = simple_query(
+            stream,
+            "SELECT t.oid, e.extname, t.typname FROM pg_catalog.pg_type AS t \
+             JOIN pg_catalog.pg_depend AS d ON d.classid = 'pg_catalog.pg_type'::regclass \
+                AND d.objid = t.oid AND d.deptype = 'e' \
+             JOIN pg_catalog.pg_extension AS e \
+                ON d.refclassid = 'pg_catalog.pg_extension'::regclass AND d.refobjid = e.oid",
+        )
+        .await?;
+
+        self.extensions.clear();
+        for row in rows {
+            let (extension, type_name) = (row[1].as_deref(), row[2].as_deref());
+            if let Some((_, _, kind)) = EXTENSION_TYPES
+                .iter()
+                .find(|(e, t, _)| Some(*e) == extension && Some(*t) == type_name)
+            {
+                self.extensions.insert(parse_oid(&row[0])?, *kind);
+            }
+        }
+        Ok(())
+    }
+
+    /// Relit les libellés des enums, qui peuvent changer sans changer d'OID
+    pub async fn reload_enum_labels(
+        &mut self,
+        stream: &mut TcpStream,
+        oids: &[Oid],
+    ) -> PgToPlResult<()> {
+        for oid in oids {
+            if let Some(pg_type) = self.types.get_mut(oid) {
+                pg_type.enum_labels.clear();
+            }
+        }
+        self.load_enum_labels(stream, oids).await
+    }
+
+    async fn load_enum_labels(&mut self, stream: &mut TcpStream, oids: &[Oid]) -> PgToPlResult<()> {
+        let rows = simple_query(
+            stream,
+            &format!(
+                "SELECT enumtypid, enumlabel FROM pg_catalog.pg_enum \
+                 WHERE enumtypid IN ({}) ORDER BY enumtypid, enumsortorder",
+                join_oids(oids)
+            ),
+        )
+        .await?;
+
+        for row in rows {
+            if let Some(pg_type) = self.types.get_mut(&parse_oid(&row[0])?) {
+                pg_type.enum_labels.push(row[1].clone().unwrap_or_default());
+            }
+        }
+        Ok(())
+    }
+
+    /// Renseigne `subtype` à partir de lignes (OID du type, OID du sous-type), renvoie les sous-types
+    async fn load_subtypes(
+        &mut self,
+        stream: &mut TcpStream,
+        query: &str,
+    ) -> PgToPlResult<Vec<Oid>> {
+        let rows = simple_query(stream, query).await?;
+
+        let mut subtypes = Vec::with_capacity(rows.len());
+        for row in rows {
+            let subtype = parse_oid(&row[1])?;
+            if let Some(pg_type) = self.types.get_mut(&parse_oid(&row[0])?) {
+                pg_type.subtype = subtype;
+            }
+            subtypes.push(subtype);
+        }
+        Ok(subtypes)
+    }
+
+    /// Charge les attributs des types composites, renvoie les OID de leurs types
+    async fn load_composite_fields(
+        &mut self,
+        stream: &mut TcpStream,
+        relids: &[Oid],
+    ) -> PgToPlResult<Vec<Oid>> {
+        let rows = simple_query(
+            stream,
+            &format!(
+                "SELECT t.oid, a.attname, a.atttypid \
+                 FROM pg_catalog.pg_attribute a \
+                 JOIN pg_catalog.pg_type t ON t.typrelid = a.attrelid \
+                 WHERE a.attrelid IN ({}) AND a.attnum > 0 AND NOT a.attisdropped \
+                 ORDER BY a.attrelid, a.attnum",
+                join_oids(relids)
+            ),
+        )
+        .await?;
+
+        let mut field_types = Vec::with_capacity(rows.len());
+        for row in rows {
+            let field_oid = parse_oid(&row[2])?;
+            if let Some(pg_type) = self.types.get_mut(&parse_oid(&row[0])?) {
+                pg_type
+                    .fields
+                    .push((row[1].clone().unwrap_or_default(), field_oid));
+            }
+            field_types.push(field_oid);
+        }
+        Ok(field_types)
+    }
+}
+
+fn join_oids<'a, I>(oids: I) -> String
+where
+    I: IntoIterator<Item = &'a Oid>,
+{
+    oids.into_iter()
+        .map(|oid| oid.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn parse_oid(value: &Option<String>) -> PgToPlResult<Oid> {
+    value
+        .as_deref()
+        .unwrap_or("0")
+        .parse()
+        .map_err(|e| PgToPlError::DecodeError(Box::new(e)))
+}
+
+fn parse_kind(value: &Option<String>) -> TypeKind {
+    match value.as_deref() {
+        Some("c") => TypeKind::Composite,
+        Some("d") => TypeKind::Domain,
+        Some("e") => TypeKind::Enum,
+        Some("p") => TypeKind::Pseudo,
+        Some("r") => TypeKind::Range,
+        Some("m") => TypeKind::Multirange,
+        _ => TypeKind::Base,
+    }
+}
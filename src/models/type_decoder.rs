@@ -0,0 +1,89 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use polars::prelude::{AnyValue, DataType, Series};
+use postgres_protocol::Oid;
+
+use crate::utils::error::PgToPlResult;
+
+/// Décodeur d'un type PostgreSQL non pris en charge nativement (types d'extension, ...)
+pub trait TypeDecoder: Send + Sync {
+    /// Type Polars de la colonne produite
+    fn dtype(&self) -> DataType;
+
+    /// Décode la valeur binaire d'une ligne non NULL et l'ajoute au builder
+    fn decode(&self, bytes: &[u8], builder: &mut ValueBuilder) -> PgToPlResult<()>;
+}
+
+/// Valeurs d'une colonne décodée par un `TypeDecoder`, converties vers `dtype` à la fin
+#[derive(Debug, Clone, Default)]
+pub struct ValueBuilder {
+    values: Vec<AnyValue<'static>>,
+}
+
+impl ValueBuilder {
+    pub fn push(&mut self, value: AnyValue<'static>) {
+        self.values.push(value);
+    }
+
+    pub fn push_null(&mut self) {
+        self.values.push(AnyValue::Null);
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub(crate) fn into_series(self, name: &str, dtype: &DataType) -> PgToPlResult<Series> {
+        Ok(Series::from_any_values_and_dtype(
+            name.into(),
+            &self.values,
+            dtype,
+            false,
+        )?)
+    }
+}
+
+/// Décodeurs personnalisés, par OID ou par nom de type (`pg_type.typname`)
+#[derive(Clone, Default)]
+pub struct TypeDecoderRegistry {
+    by_oid: HashMap<Oid, Arc<dyn TypeDecoder>>,
+    by_name: HashMap<String, Arc<dyn TypeDecoder>>,
+}
+
+impl TypeDecoderRegistry {
+    pub fn register_oid<D>(&mut self, oid: Oid, decoder: D)
+    where
+        D: TypeDecoder + 'static,
+    {
+        self.by_oid.insert(oid, Arc::new(decoder));
+    }
+
+    /// Le nom n'est consulté que pour les types lus dans le catalogue (non natifs)
+    pub fn register_name<D>(&mut self, name: &str, decoder: D)
+    where
+        D: TypeDecoder + 'static,
+    {
+        self.by_name.insert(String::from(name), Arc::new(decoder));
+    }
+
+    pub fn by_oid(&self, oid: Oid) -> Option<Arc<dyn TypeDecoder>> {
+        self.by_oid.get(&oid).cloned()
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<Arc<dyn TypeDecoder>> {
+        self.by_name.get(name).cloned()
+    }
+}
+
+impl fmt::Debug for TypeDecoderRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypeDecoderRegistry")
+            .field("oids", &self.by_oid.keys().collect::<Vec<_>>())
+            .field("names", &self.by_name.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
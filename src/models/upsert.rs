@@ -0,0 +1,98 @@
+use crate::utils::quote_identifier;
+
+/// Requête utilisée par `Client::upsert_dataframe`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UpsertStrategy {
+    /// `INSERT ... ON CONFLICT (clés) DO UPDATE`, nécessite une contrainte unique sur les clés
+    #[default]
+    OnConflict,
+    /// `MERGE`, PostgreSQL 15 ou plus
+    Merge,
+}
+
+/// Lignes insérées et mises à jour par `Client::upsert_dataframe`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UpsertResult {
+    pub inserted: u64,
+    pub updated: u64,
+}
+
+/// Colonnes et clés, déjà entre guillemets
+pub(crate) struct UpsertColumns {
+    pub columns: Vec<String>,
+    pub keys: Vec<String>,
+}
+
+impl UpsertColumns {
+    pub fn new(columns: &[String], keys: &[&str]) -> Self {
+        Self {
+            columns: columns.iter().map(|c| quote_identifier(c)).collect(),
+            keys: keys.iter().map(|k| quote_identifier(k)).collect(),
+        }
+    }
+
+    fn updated(&self) -> impl Iterator<Item = &String> {
+        self.columns.iter().filter(|c| !self.keys.contains(c))
+    }
+
+    /// Sans colonne hors clés, une ligne existante est laissée telle quelle
+    pub fn has_updates(&self) -> bool {
+        self.updated().next().is_some()
+    }
+
+    /// `INSERT ... ON CONFLICT` renvoyant les compteurs ; `xmax = 0` pour une ligne insérée
+    pub fn on_conflict(&self, table: &str, staging: &str) -> String {
+        let columns = self.columns.join(", ");
+        let updates: Vec<String> = self
+            .updated()
+            .map(|c| format!("{} = EXCLUDED.{}", c, c))
+            .collect();
+        let action = if updates.is_empty() {
+            String::from("DO NOTHING")
+        } else {
+            format!("DO UPDATE SET {}", updates.join(", "))
+        };
+        format!(
+            "WITH upserted AS (
+                INSERT INTO {table} ({columns}) SELECT {columns} FROM {staging}
+                ON CONFLICT ({keys}) {action}
+                RETURNING (xmax = 0) AS inserted
+            )
+            SELECT count(*) FILTER (WHERE inserted), count(*) FILTER (WHERE NOT inserted)
+            FROM upserted",
+            keys = self.keys.join(", "),
+        )
+    }
+
+    /// `MERGE` précédé d'une CTE qui écrit dans `counts` le nombre de lignes de `staging`
+    /// ayant une correspondance dans `table`, compté dans le même instantané que le `MERGE`
+    pub fn merge(&self, table: &str, staging: &str, counts: &str) -> String {
+        let updates: Vec<String> = self.updated().map(|c| format!("{} = s.{}", c, c)).collect();
+        let matched = if updates.is_empty() {
+            String::new()
+        } else {
+            format!("WHEN MATCHED THEN UPDATE SET {} ", updates.join(", "))
+        };
+        let values: Vec<String> = self.columns.iter().map(|c| format!("s.{}", c)).collect();
+        format!(
+            "WITH matched AS (
+                INSERT INTO {counts}
+                SELECT count(*) FROM {staging} AS s
+                WHERE EXISTS (SELECT 1 FROM {table} AS t WHERE {condition})
+            )
+            MERGE INTO {table} AS t USING {staging} AS s ON {condition}
+            {matched}WHEN NOT MATCHED THEN INSERT ({columns}) VALUES ({values})",
+            condition = self.key_condition(),
+            columns = self.columns.join(", "),
+            values = values.join(", "),
+        )
+    }
+
+    fn key_condition(&self) -> String {
+        self.keys
+            .iter()
+            .map(|k| format!("t.{} = s.{}", k, k))
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    }
+}
@@ -0,0 +1,191 @@
+use bytes::{Buf, BufMut, BytesMut};
+
+use crate::utils::error::{PgToPlError, PgToPlResult};
+
+/// Signature de l'en-tête du format binaire de COPY
+const COPY_SIGNATURE: &[u8; 11] = b"PGCOPY\n\xff\r\n\0";
+
+/// En-tête : signature, flags puis longueur de l'extension, tous deux nuls
+pub fn write_copy_header(buf: &mut BytesMut) {
+    buf.put_slice(COPY_SIGNATURE);
+    buf.put_i32(0);
+    buf.put_i32(0);
+}
+
+/// Nombre de champs, puis chaque champ préfixé par sa longueur (-1 = NULL)
+pub fn write_copy_tuple<'a, I>(fields: I, field_count: usize, buf: &mut BytesMut)
+where
+    I: IntoIterator<Item = Option<&'a [u8]>>,
+{
+    buf.put_i16(field_count as i16);
+    for field in fields {
+        match field {
+            Some(bytes) => {
+                buf.put_i32(bytes.len() as i32);
+                buf.put_slice(bytes);
+            }
+            None => buf.put_i32(-1),
+        }
+    }
+}
+
+/// Fin des données : un nombre de champs à -1
+pub fn write_copy_trailer(buf: &mut BytesMut) {
+    buf.put_i16(-1);
+}
+
+/// Lecture incrémentale du format binaire de COPY : les messages CopyData
+/// ne coïncident pas forcément avec les lignes
+#[derive(Debug, Default)]
+pub struct CopyReader {
+    pending: BytesMut,
+    header_read: bool,
+    finished: bool,
+}
+
+impl CopyReader {
+    /// Marqueur de fin lu
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Décode les lignes complètes de `data`, précédé des octets en attente.
+    /// Une ligne incomplète est conservée jusqu'au message suivant.
+    pub fn read_rows<F>(&mut self, data: &[u8], on_row: F) -> PgToPlResult<()>
+    where
+        F: FnMut(&[Option<&[u8]>]) -> PgToPlResult<()>,
+    {
+        // cas courant : une ligne par message, lue sans copie
+        if self.pending.is_empty() {
+            let consumed = self.parse(data, on_row)?;
+            self.pending.extend_from_slice(&data[consumed..]);
+        } else {
+            self.pending.extend_from_slice(data);
+            let pending = std::mem::take(&mut self.pending);
+            let consumed = self.parse(&pending, on_row)?;
+            self.pending = pending;
+            self.pending.advance(consumed);
+        }
+        Ok(())
+    }
+
+    /// Renvoie le nombre d'octets consommés
+    fn parse<F>(&mut self, buffer: &[u8], mut on_row: F) -> PgToPlResult<usize>
+    where
+        F: FnMut(&[Option<&[u8]>]) -> PgToPlResult<()>,
+    {
+        let mut position = 0;
+        if !self.header_read {
+            let header_len = COPY_SIGNATURE.len() + 8;
+            if buffer.len() < header_len {
+                return Ok(0);
+            }
+            if &buffer[..COPY_SIGNATURE.len()] != COPY_SIGNATURE {
+                return Err(PgToPlError::InvalidCopyData("bad signature"));
+            }
+            let extension_len = read_i32(buffer, header_len - 4).unwrap();
+            let end = usize::try_from(extension_len)
+                .ok()
+                .and_then(|len| header_len.checked_add(len))
+                .ok_or(PgToPlError::InvalidCopyData("bad header extension length"))?;
+            if buffer.len() < end {
+                return Ok(0);
+            }
+            position = end;
+            self.header_read = true;
+        }
+
+        let mut fields = Vec::new();
+        while !self.finished {
+            let Some(field_count) = read_i16(buffer, position) else {
+                break;
+            };
+            if field_count == -1 {
+                self.finished = true;
+                position += 2;
+                break;
+            }
+
+            fields.clear();
+            let mut cursor = position + 2;
+            let mut complete = true;
+            for _ in 0..field_count {
+                let Some(len) = read_i32(buffer, cursor) else {
+                    complete = false;
+                    break;
+                };
+                cursor += 4;
+                if len < 0 {
+                    fields.push(None);
+                    continue;
+                }
+                let end = cursor + len as usize;
+                if end > buffer.len() {
+                    complete = false;
+                    break;
+                }
+                fields.push(Some(&buffer[cursor..end]));
+                cursor = end;
+            }
+            if !complete {
+                break;
+            }
+            on_row(&fields)?;
+            position = cursor;
+        }
+
+        Ok(position)
+    }
+}
+
+fn read_i16(buffer: &[u8], position: usize) -> Option<i16> {
+    let bytes = buffer.get(position..position + 2)?;
+    Some(i16::from_be_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_i32(buffer: &[u8], position: usize) -> Option<i32> {
+    let bytes = buffer.get(position..position + 4)?;
+    Some(i32::from_be_bytes(bytes.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_rows() {
+        let mut data = BytesMut::new();
+        write_copy_header(&mut data);
+        write_copy_tuple([Some(&b"ab"[..]), None], 2, &mut data);
+        write_copy_trailer(&mut data);
+
+        // un message coupé au milieu d'une ligne
+        let mut reader = CopyReader::default();
+        let mut rows = Vec::new();
+        for chunk in [&data[..20], &data[20..]] {
+            reader
+                .read_rows(chunk, |fields| {
+                    rows.push(
+                        fields
+                            .iter()
+                            .map(|f| f.map(<[u8]>::to_vec))
+                            .collect::<Vec<_>>(),
+                    );
+                    Ok(())
+                })
+                .unwrap();
+        }
+        assert!(reader.is_finished());
+        assert_eq!(rows, vec![vec![Some(b"ab".to_vec()), None]]);
+    }
+
+    #[test]
+    fn test_negative_header_extension() {
+        let mut data = BytesMut::new();
+        data.put_slice(COPY_SIGNATURE);
+        data.put_i32(0);
+        data.put_i32(-8);
+        let result = CopyReader::default().read_rows(&data, |_| Ok(()));
+        assert!(matches!(result, Err(PgToPlError::InvalidCopyData(_))));
+    }
+}
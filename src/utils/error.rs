@@ -4,8 +4,8 @@ use thiserror::Error;
 pub enum PgToPlError {
     #[error("I/O error {0}")]
     Io(#[from] std::io::Error),
-    #[error("Only 1D arrays supported")]
-    OnlyOneDimensionArraySupported,
+    #[error("Arrays of a column must share the same number of dimensions: expected {0}, got {1}")]
+    ArrayDimensionMismatch(usize, usize),
     #[error("Not enough bytes")]
     NotEnoughBytes,
     #[error("Bind error")]
@@ -22,8 +22,48 @@ pub enum PgToPlError {
     PoolError(String),
     #[error("Parameter type mismatch")]
     ParamTypeMismatch,
+    #[error("Parameter count mismatch: expected {0}, got {1}")]
+    ParamCountMismatch(usize, usize),
+    #[error("Cannot convert parameter of type {0} to server type {1}")]
+    ParamCoercion(u32, u32),
+    #[error("Missing named parameter: {0}")]
+    MissingNamedParam(String),
+    #[error("Named and positional ($n) parameters cannot be mixed")]
+    MixedParams,
+    #[error("Invalid binary COPY data: {0}")]
+    InvalidCopyData(&'static str),
+    #[error("No PostgreSQL type for Polars data type {0}")]
+    UnsupportedDataType(String),
+    #[error("Column name {0} is used more than once")]
+    DuplicateColumn(String),
+    #[error("Polars error: {0}")]
+    Polars(#[from] polars::prelude::PolarsError),
     #[error("Query error: {0}")]
     QueryError(String),
+    #[error("Decode error: {0}")]
+    DecodeError(#[from] Box<dyn std::error::Error + Sync + Send>),
+    #[error("Numeric value does not fit in a 128 bits decimal")]
+    NumericOverflow,
+    #[error("NaN and Infinity numeric values have no decimal representation")]
+    NumericNotFinite,
+    #[error("Record field count mismatch: expected {0}, got {1}")]
+    RecordFieldCountMismatch(usize, usize),
+    #[error("Record field type mismatch: expected {0}, got {1}")]
+    RecordFieldTypeMismatch(u32, u32),
+    #[error("Unknown enum label: {0}")]
+    UnknownEnumLabel(String),
+    #[error("Array element type mismatch: expected {0}, got {1}")]
+    ArrayElementTypeMismatch(u32, u32),
+    #[error("Upsert requires at least one key column")]
+    MissingKeyColumns,
+}
+
+impl PgToPlError {
+    /// Erreur d'E/S ou de protocole : le flux n'est plus synchronisé. Une erreur renvoyée
+    /// par le serveur laisse au contraire la connexion utilisable.
+    pub(crate) fn breaks_connection(&self) -> bool {
+        matches!(self, PgToPlError::Io(_) | PgToPlError::ConnectionClosed)
+    }
 }
 
 pub type PgToPlResult<T> = Result<T, PgToPlError>;
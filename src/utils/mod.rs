@@ -1,8 +1,15 @@
 use fallible_iterator::FallibleIterator;
 use postgres_protocol::message::backend::ErrorResponseBody;
 
+pub mod copy;
 pub mod error;
-pub mod text_array;
+pub mod named_params;
+pub mod numeric;
+pub mod range;
+pub mod record;
+pub mod tsvector;
+pub mod vector;
+pub mod wkb;
 
 pub fn md5_hash(user: &str, password: &str, salt: &[u8; 4]) -> String {
     // Étape 1 : md5(password + username)
@@ -26,6 +33,11 @@ pub fn statement_name(query: &str) -> String {
     format!("stmt_{:x}", digest) // Toujours 32 caractères
 }
 
+/// Identifiant SQL entre guillemets, guillemets internes doublés
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
 pub fn print_error(err: &ErrorResponseBody) {
     println!("Received error: {:?}", error_to_string(err));
 }
@@ -37,7 +49,7 @@ pub fn error_to_string(err: &ErrorResponseBody) -> String {
         match field {
             Ok(f) => {
                 let bytes = f.value_bytes();
-                let value = String::from_utf8_lossy(&bytes);
+                let value = String::from_utf8_lossy(bytes);
 
                 error_strings.push(value.to_string());
             }
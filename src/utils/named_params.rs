@@ -0,0 +1,224 @@
+use crate::{PgToPlError, utils::error::PgToPlResult};
+
+/// Réécrit les paramètres nommés (`:nom` ou `$nom`) en paramètres positionnels (`$n`).
+/// Un même nom réutilise le même numéro. Les chaînes, identifiants entre guillemets,
+/// commentaires et corps dollar-quotés sont recopiés tels quels.
+/// Dans un indice entre crochets, `:` sépare les bornes d'une tranche (`arr[lo:hi]`,
+/// `arr[:hi]`) : seul `$nom` y est un paramètre, `ARRAY[:a, :b]` reste réécrit.
+/// Un `$n` positionnel mêlé à des paramètres nommés est refusé.
+/// Renvoie la requête réécrite et les noms dans l'ordre des numéros.
+pub fn rewrite_named_params(query: &str) -> PgToPlResult<(String, Vec<String>)> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut sql = String::with_capacity(query.len());
+    let mut names: Vec<String> = Vec::new();
+    let mut positional = false;
+    let mut brackets: Vec<bool> = Vec::new(); // vrai pour un indice, faux pour `ARRAY[...]`
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        let end = match (c, next) {
+            // E'...' : échappements par antislash
+            ('e' | 'E', Some('\'')) if !is_ident_char(i.checked_sub(1).map(|p| chars[p])) => {
+                skip_quoted(&chars, i + 1, '\'', true)
+            }
+            ('\'', _) => skip_quoted(&chars, i, '\'', false),
+            ('"', _) => skip_quoted(&chars, i, '"', false),
+            ('-', Some('-')) => chars[i..]
+                .iter()
+                .position(|c| *c == '\n')
+                .map_or(chars.len(), |p| i + p + 1),
+            ('/', Some('*')) => skip_block_comment(&chars, i),
+            ('$', _) => match dollar_tag(&chars, i) {
+                Some(tag_len) => skip_dollar_quoted(&chars, i, tag_len),
+                None => i,
+            },
+            _ => i,
+        };
+        if end > i {
+            sql.extend(&chars[i..end]);
+            i = end;
+            continue;
+        }
+
+        // `:nom` (mais pas le cast `::type` ni une borne de tranche) ou `$nom`
+        let is_placeholder = match c {
+            ':' => {
+                brackets.last() != Some(&true)
+                    && i.checked_sub(1).map(|p| chars[p]) != Some(':')
+                    && next != Some(':')
+                    && next.is_some_and(is_ident_start)
+            }
+            '$' => next.is_some_and(is_ident_start),
+            _ => false,
+        };
+        match c {
+            '[' => brackets.push(is_subscript(&chars, i)),
+            ']' => {
+                brackets.pop();
+            }
+            '$' if next.is_some_and(|c| c.is_ascii_digit()) => positional = true,
+            _ => {}
+        }
+        if is_placeholder {
+            let name_end = (i + 1..chars.len())
+                .find(|p| !is_ident_char(Some(chars[*p])))
+                .unwrap_or(chars.len());
+            let name: String = chars[i + 1..name_end].iter().collect();
+            let slot = match names.iter().position(|n| *n == name) {
+                Some(p) => p + 1,
+                None => {
+                    names.push(name);
+                    names.len()
+                }
+            };
+            sql.push_str(&format!("${}", slot));
+            i = name_end;
+            continue;
+        }
+
+        sql.push(c);
+        i += 1;
+    }
+
+    if positional && !names.is_empty() {
+        return Err(PgToPlError::MixedParams);
+    }
+    Ok((sql, names))
+}
+
+/// Un `[` suivant une expression (nom, `)`, `]`, `"...")` ouvre un indice, sauf après `ARRAY`
+fn is_subscript(chars: &[char], bracket: usize) -> bool {
+    let Some(end) = chars[..bracket].iter().rposition(|c| !c.is_whitespace()) else {
+        return false;
+    };
+    match chars[end] {
+        ')' | ']' | '"' => true,
+        c if is_ident_char(Some(c)) => {
+            let start = chars[..end]
+                .iter()
+                .rposition(|c| !is_ident_char(Some(*c)))
+                .map_or(0, |p| p + 1);
+            let word: String = chars[start..=end].iter().collect();
+            !word.eq_ignore_ascii_case("array")
+        }
+        _ => false,
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: Option<char>) -> bool {
+    c.is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Position après le guillemet fermant ; un guillemet doublé est un échappement
+fn skip_quoted(chars: &[char], start: usize, quote: char, backslash_escapes: bool) -> usize {
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if backslash_escapes => i += 2,
+            c if c == quote && chars.get(i + 1) == Some(&quote) => i += 2,
+            c if c == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    chars.len()
+}
+
+/// Les commentaires `/* */` peuvent être imbriqués
+fn skip_block_comment(chars: &[char], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i < chars.len() {
+        match (chars[i], chars.get(i + 1)) {
+            ('/', Some('*')) => {
+                depth += 1;
+                i += 2;
+            }
+            ('*', Some('/')) => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    chars.len()
+}
+
+/// Longueur du délimiteur `$tag$` (ou `$$`) commençant en `start`, `None` si ce n'en est pas un
+fn dollar_tag(chars: &[char], start: usize) -> Option<usize> {
+    // `$1$` ou `a$b` ne sont pas des délimiteurs
+    if is_ident_char(start.checked_sub(1).map(|p| chars[p])) {
+        return None;
+    }
+    let mut i = start + 1;
+    if chars
+        .get(i)
+        .is_some_and(|c| !is_ident_start(*c) && *c != '$')
+    {
+        return None;
+    }
+    while i < chars.len() && is_ident_char(Some(chars[i])) {
+        i += 1;
+    }
+    (chars.get(i) == Some(&'$')).then_some(i + 1 - start)
+}
+
+fn skip_dollar_quoted(chars: &[char], start: usize, tag_len: usize) -> usize {
+    let tag = &chars[start..start + tag_len];
+    let body = start + tag_len;
+    (body..chars.len())
+        .find(|p| chars[*p..].starts_with(tag))
+        .map_or(chars.len(), |p| p + tag_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rewrite(query: &str) -> (String, Vec<String>) {
+        rewrite_named_params(query).unwrap()
+    }
+
+    #[test]
+    fn test_array_slices() {
+        let (sql, names) = rewrite("SELECT arr[lo:hi], arr[:hi], arr[2:], m[1:2][:n] FROM t");
+        assert_eq!(
+            sql,
+            "SELECT arr[lo:hi], arr[:hi], arr[2:], m[1:2][:n] FROM t"
+        );
+        assert!(names.is_empty());
+
+        let (sql, names) = rewrite("SELECT (f(x))[:a], arr[$lo:$hi], ARRAY[:a, :b], array [:b]");
+        assert_eq!(
+            sql,
+            "SELECT (f(x))[:a], arr[$1:$2], ARRAY[$3, $4], array [$4]"
+        );
+        assert_eq!(names, vec!["lo", "hi", "a", "b"]);
+    }
+
+    #[test]
+    fn test_mixed_placeholders() {
+        assert!(matches!(
+            rewrite_named_params("SELECT $1, :name"),
+            Err(PgToPlError::MixedParams)
+        ));
+
+        // `$1` dans une chaîne ou un corps dollar-quoté n'est pas un paramètre
+        let (sql, names) = rewrite("SELECT '$1', $f$ $1 $f$, :name");
+        assert_eq!(sql, "SELECT '$1', $f$ $1 $f$, $1");
+        assert_eq!(names, vec!["name"]);
+
+        let (sql, names) = rewrite("SELECT $1::int4");
+        assert_eq!(sql, "SELECT $1::int4");
+        assert!(names.is_empty());
+    }
+}
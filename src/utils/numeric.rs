@@ -0,0 +1,132 @@
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::{PgToPlError, utils::error::PgToPlResult};
+
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xC000;
+const NUMERIC_PINF: u16 = 0xD000;
+const NUMERIC_NINF: u16 = 0xF000;
+
+/// Valeur `numeric` ramenée à un entier : `mantissa * 10^-scale`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Numeric {
+    pub mantissa: i128,
+    pub scale: u16,
+}
+
+impl Numeric {
+    /// Change l'échelle de la valeur, `None` en cas de dépassement
+    pub fn rescale(self, scale: u16) -> Option<i128> {
+        if scale >= self.scale {
+            10i128
+                .checked_pow((scale - self.scale) as u32)
+                .and_then(|factor| self.mantissa.checked_mul(factor))
+        } else {
+            10i128
+                .checked_pow((self.scale - scale) as u32)
+                .map(|factor| self.mantissa / factor)
+        }
+    }
+}
+
+/// Décode le format binaire de `numeric` (chiffres en base 10000).
+/// NaN et ±Infinity n'ont pas d'équivalent en décimal : `NumericNotFinite`, lu comme NULL
+/// dans les colonnes.
+pub fn parse_numeric(mut bytes: &[u8]) -> PgToPlResult<Numeric> {
+    let ndigits = bytes.read_i16::<BigEndian>()?;
+    let weight = bytes.read_i16::<BigEndian>()? as i32;
+    let sign = bytes.read_u16::<BigEndian>()?;
+    let dscale = bytes.read_u16::<BigEndian>()?;
+
+    if matches!(sign, NUMERIC_NAN | NUMERIC_PINF | NUMERIC_NINF) {
+        return Err(PgToPlError::NumericNotFinite);
+    }
+
+    let mut mantissa: i128 = 0;
+    for _ in 0..ndigits {
+        let digit = bytes.read_i16::<BigEndian>()? as i128;
+        mantissa = mantissa
+            .checked_mul(10_000)
+            .and_then(|m| m.checked_add(digit))
+            .ok_or(PgToPlError::NumericOverflow)?;
+    }
+
+    // mantissa vaut actuellement valeur * 10^(4 * (ndigits - 1 - weight))
+    let digits_scale = 4 * (ndigits as i32 - 1 - weight);
+    let value = if digits_scale >= 0 {
+        Numeric {
+            mantissa,
+            scale: digits_scale as u16,
+        }
+    } else {
+        let factor = 10i128
+            .checked_pow((-digits_scale) as u32)
+            .ok_or(PgToPlError::NumericOverflow)?;
+        Numeric {
+            mantissa: mantissa
+                .checked_mul(factor)
+                .ok_or(PgToPlError::NumericOverflow)?,
+            scale: 0,
+        }
+    };
+    let mut mantissa = value.rescale(dscale).ok_or(PgToPlError::NumericOverflow)?;
+
+    if sign == NUMERIC_NEG {
+        mantissa = -mantissa;
+    }
+
+    Ok(Numeric {
+        mantissa,
+        scale: dscale,
+    })
+}
+
+/// Encode `mantissa * 10^-scale` au format binaire `numeric` (chiffres en base 10000)
+pub fn encode_numeric(mantissa: i128, scale: u16) -> Vec<u8> {
+    let digits = mantissa.unsigned_abs().to_string();
+    let scale = scale as usize;
+    let (int_part, frac_part) = if digits.len() > scale {
+        digits.split_at(digits.len() - scale)
+    } else {
+        ("", digits.as_str())
+    };
+
+    // groupes de 4 chiffres alignés sur la virgule
+    let int_padding = (4 - int_part.len() % 4) % 4;
+    let frac_padding = (4 - scale % 4) % 4;
+    let aligned = format!(
+        "{}{}{:0>width$}{}",
+        "0".repeat(int_padding),
+        int_part,
+        frac_part,
+        "0".repeat(frac_padding),
+        width = scale
+    );
+    let mut groups: Vec<i16> = aligned
+        .as_bytes()
+        .chunks(4)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap().parse().unwrap())
+        .collect();
+    let mut weight = ((int_padding + int_part.len()) / 4) as i16 - 1;
+
+    let leading_zeros = groups.iter().take_while(|group| **group == 0).count();
+    groups.drain(..leading_zeros);
+    weight -= leading_zeros as i16;
+    while groups.last() == Some(&0) {
+        groups.pop();
+    }
+    if groups.is_empty() {
+        weight = 0;
+    }
+
+    let sign = if mantissa < 0 { NUMERIC_NEG } else { 0 };
+    let mut bytes = Vec::with_capacity(8 + groups.len() * 2);
+    bytes.extend((groups.len() as i16).to_be_bytes());
+    bytes.extend(weight.to_be_bytes());
+    bytes.extend(sign.to_be_bytes());
+    bytes.extend((scale as u16).to_be_bytes());
+    for group in groups {
+        bytes.extend(group.to_be_bytes());
+    }
+    bytes
+}
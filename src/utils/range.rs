@@ -0,0 +1,41 @@
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::{PgToPlError, utils::error::PgToPlResult};
+
+/// Découpe le format binaire d'un multirange : nombre de ranges, puis longueur/valeur par range
+pub fn parse_multirange(mut bytes: &[u8]) -> PgToPlResult<Vec<&[u8]>> {
+    let range_count = bytes.read_i32::<BigEndian>()?;
+    // nombre non fiable : au plus un range par 4 octets restants (longueur)
+    let mut ranges = Vec::with_capacity((range_count.max(0) as usize).min(bytes.len() / 4));
+
+    for _ in 0..range_count {
+        let len = bytes.read_i32::<BigEndian>()? as usize;
+        if bytes.len() < len {
+            return Err(PgToPlError::NotEnoughBytes);
+        }
+        let (range, rest) = bytes.split_at(len);
+        bytes = rest;
+        ranges.push(range);
+    }
+
+    Ok(ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_multirange() {
+        let mut bytes = 2i32.to_be_bytes().to_vec();
+        for range in [&[1u8][..], &[2, 3]] {
+            bytes.extend_from_slice(&(range.len() as i32).to_be_bytes());
+            bytes.extend_from_slice(range);
+        }
+        assert_eq!(parse_multirange(&bytes).unwrap(), vec![&[1u8][..], &[2, 3]]);
+
+        // nombre de ranges corrompu : erreur sans allocation démesurée
+        let bytes = i32::MAX.to_be_bytes();
+        assert!(matches!(parse_multirange(&bytes), Err(PgToPlError::Io(_))));
+    }
+}
@@ -0,0 +1,51 @@
+use byteorder::{BigEndian, ReadBytesExt};
+use postgres_protocol::Oid;
+
+use crate::{PgToPlError, utils::error::PgToPlResult};
+
+/// Champ d'un record : OID du type et valeur (None = NULL)
+pub type RecordField<'a> = (Oid, Option<&'a [u8]>);
+
+/// Décode le format binaire d'un record : nombre de champs, puis OID/longueur/valeur par champ
+pub fn parse_record(mut bytes: &[u8]) -> PgToPlResult<Vec<RecordField<'_>>> {
+    let field_count = bytes.read_i32::<BigEndian>()?;
+    // nombre non fiable : au plus un champ par 8 octets restants (OID + longueur)
+    let mut fields = Vec::with_capacity((field_count.max(0) as usize).min(bytes.len() / 8));
+
+    for _ in 0..field_count {
+        let oid = bytes.read_u32::<BigEndian>()?;
+        let len = bytes.read_i32::<BigEndian>()?;
+        if len < 0 {
+            fields.push((oid, None));
+        } else {
+            let len = len as usize;
+            if bytes.len() < len {
+                return Err(PgToPlError::NotEnoughBytes);
+            }
+            let (value, rest) = bytes.split_at(len);
+            bytes = rest;
+            fields.push((oid, Some(value)));
+        }
+    }
+
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_record() {
+        let mut bytes = 1i32.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&23u32.to_be_bytes());
+        bytes.extend_from_slice(&4i32.to_be_bytes());
+        bytes.extend_from_slice(&7i32.to_be_bytes());
+        let fields = parse_record(&bytes).unwrap();
+        assert_eq!(fields, vec![(23, Some(&7i32.to_be_bytes()[..]))]);
+
+        // nombre de champs corrompu : erreur sans allocation démesurée
+        let bytes = i32::MAX.to_be_bytes();
+        assert!(matches!(parse_record(&bytes), Err(PgToPlError::Io(_))));
+    }
+}
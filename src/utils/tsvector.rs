@@ -0,0 +1,68 @@
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::{PgToPlError, utils::error::PgToPlResult};
+
+/// Lexème d'un tsvector, avec ses positions et leur poids (`A` à `D`)
+pub struct Lexeme<'a> {
+    pub text: &'a str,
+    pub positions: Vec<(u16, char)>,
+}
+
+/// Format binaire : nombre de lexèmes, puis pour chacun le texte terminé par un octet nul,
+/// le nombre de positions (u16) et les positions (u16, poids dans les 2 bits de poids fort)
+pub fn parse_tsvector(mut bytes: &[u8]) -> PgToPlResult<Vec<Lexeme<'_>>> {
+    let count = bytes.read_i32::<BigEndian>()?;
+    // nombre non fiable : au plus un lexème par 3 octets restants (octet nul + nombre de positions)
+    let mut lexemes = Vec::with_capacity((count.max(0) as usize).min(bytes.len() / 3));
+
+    for _ in 0..count {
+        let end = bytes
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or(PgToPlError::NotEnoughBytes)?;
+        let text = std::str::from_utf8(&bytes[..end])
+            .map_err(|e| PgToPlError::DecodeError(Box::new(e)))?;
+        bytes = &bytes[end + 1..];
+
+        let position_count = bytes.read_u16::<BigEndian>()?;
+        let mut positions = Vec::with_capacity((position_count as usize).min(bytes.len() / 2));
+        for _ in 0..position_count {
+            let entry = bytes.read_u16::<BigEndian>()?;
+            let weight = match entry >> 14 {
+                3 => 'A',
+                2 => 'B',
+                1 => 'C',
+                _ => 'D',
+            };
+            positions.push((entry & 0x3fff, weight));
+        }
+
+        lexemes.push(Lexeme { text, positions });
+    }
+
+    Ok(lexemes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tsvector() {
+        let mut bytes = 1i32.to_be_bytes().to_vec();
+        bytes.extend_from_slice(b"cat\0");
+        bytes.extend_from_slice(&1u16.to_be_bytes());
+        bytes.extend_from_slice(&(0xC000u16 | 3).to_be_bytes());
+        let lexemes = parse_tsvector(&bytes).unwrap();
+        assert_eq!(lexemes[0].text, "cat");
+        assert_eq!(lexemes[0].positions, vec![(3, 'A')]);
+
+        // nombres corrompus : erreur sans allocation démesurée
+        let bytes = i32::MAX.to_be_bytes();
+        assert!(parse_tsvector(&bytes).is_err());
+        let mut bytes = 1i32.to_be_bytes().to_vec();
+        bytes.extend_from_slice(b"cat\0");
+        bytes.extend_from_slice(&u16::MAX.to_be_bytes());
+        assert!(parse_tsvector(&bytes).is_err());
+    }
+}
@@ -0,0 +1,166 @@
+use byteorder::{BigEndian, ReadBytesExt};
+
+use crate::{PgToPlError, utils::error::PgToPlResult};
+
+/// pgvector `vector` : dimension (u16), champ inutilisé (u16), puis les float4
+pub fn parse_vector(mut bytes: &[u8]) -> PgToPlResult<Vec<f32>> {
+    let dim = bytes.read_u16::<BigEndian>()? as usize;
+    bytes.read_u16::<BigEndian>()?;
+    if bytes.len() != 4 * dim {
+        return Err(PgToPlError::NotEnoughBytes);
+    }
+
+    let mut values = Vec::with_capacity(dim);
+    for _ in 0..dim {
+        values.push(bytes.read_f32::<BigEndian>()?);
+    }
+    Ok(values)
+}
+
+/// pgvector `halfvec` : même entête que `vector`, valeurs en float16
+pub fn parse_halfvec(mut bytes: &[u8]) -> PgToPlResult<Vec<f32>> {
+    let dim = bytes.read_u16::<BigEndian>()? as usize;
+    bytes.read_u16::<BigEndian>()?;
+    if bytes.len() != 2 * dim {
+        return Err(PgToPlError::NotEnoughBytes);
+    }
+
+    let mut values = Vec::with_capacity(dim);
+    for _ in 0..dim {
+        values.push(f16_to_f32(bytes.read_u16::<BigEndian>()?));
+    }
+    Ok(values)
+}
+
+/// pgvector `sparsevec` : dimension, nombre de valeurs non nulles, champ inutilisé (i32),
+/// puis les indices (base 0) et les float4 ; renvoie le vecteur dense
+pub fn parse_sparsevec(mut bytes: &[u8]) -> PgToPlResult<Vec<f32>> {
+    let dim = bytes.read_i32::<BigEndian>()?;
+    let nnz = bytes.read_i32::<BigEndian>()?;
+    bytes.read_i32::<BigEndian>()?;
+
+    // tailles vérifiées avant toute allocation
+    if dim < 0 || nnz < 0 || nnz > dim {
+        return Err(invalid_sparsevec(format!("dim {} and nnz {}", dim, nnz)));
+    }
+    let (dim, nnz) = (dim as usize, nnz as usize);
+    if bytes.len() != 8 * nnz {
+        return Err(PgToPlError::NotEnoughBytes);
+    }
+
+    let mut indices = Vec::with_capacity(nnz);
+    for _ in 0..nnz {
+        indices.push(bytes.read_i32::<BigEndian>()?);
+    }
+
+    let mut values = vec![0.0; dim];
+    for index in indices {
+        let value = bytes.read_f32::<BigEndian>()?;
+        let slot = usize::try_from(index)
+            .ok()
+            .and_then(|index| values.get_mut(index))
+            .ok_or_else(|| invalid_sparsevec(format!("index {} out of dim {}", index, dim)))?;
+        *slot = value;
+    }
+    Ok(values)
+}
+
+fn invalid_sparsevec(detail: String) -> PgToPlError {
+    PgToPlError::DecodeError(format!("Invalid sparsevec: {}", detail).into())
+}
+
+fn f16_to_f32(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exponent = ((half >> 10) & 0x1f) as u32;
+    let mantissa = (half & 0x03ff) as u32;
+
+    let bits = match exponent {
+        0 if mantissa == 0 => sign,
+        // sous-normal : valeur = mantissa * 2^-24
+        0 => {
+            let value = mantissa as f32 * (-24f32).exp2();
+            return if sign == 0 { value } else { -value };
+        }
+        0x1f => sign | 0x7f80_0000 | (mantissa << 13), // infini, NaN
+        _ => sign | ((exponent + 127 - 15) << 23) | (mantissa << 13),
+    };
+    f32::from_bits(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sparsevec_bytes(dim: i32, nnz: i32, indices: &[i32], values: &[f32]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for header in [dim, nnz, 0] {
+            bytes.extend_from_slice(&header.to_be_bytes());
+        }
+        for index in indices {
+            bytes.extend_from_slice(&index.to_be_bytes());
+        }
+        for value in values {
+            bytes.extend_from_slice(&value.to_be_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_parse_vector() {
+        let mut bytes = vec![0, 2, 0, 0];
+        bytes.extend_from_slice(&1.5f32.to_be_bytes());
+        bytes.extend_from_slice(&(-2.0f32).to_be_bytes());
+        assert_eq!(parse_vector(&bytes).unwrap(), vec![1.5, -2.0]);
+
+        assert!(matches!(
+            parse_vector(&bytes[..8]),
+            Err(PgToPlError::NotEnoughBytes)
+        ));
+    }
+
+    #[test]
+    fn test_parse_halfvec() {
+        // 1.0, -2.0, 0.5, plus petit sous-normal, infini
+        let bytes = [
+            0, 5, 0, 0, 0x3c, 0x00, 0xc0, 0x00, 0x38, 0x00, 0x00, 0x01, 0x7c, 0x00,
+        ];
+        let values = parse_halfvec(&bytes).unwrap();
+        assert_eq!(values[..3], [1.0, -2.0, 0.5]);
+        assert_eq!(values[3], (-24f32).exp2());
+        assert_eq!(values[4], f32::INFINITY);
+        assert!(f16_to_f32(0x7e00).is_nan());
+        assert_eq!(f16_to_f32(0x8000).to_bits(), (-0.0f32).to_bits());
+    }
+
+    #[test]
+    fn test_parse_sparsevec() {
+        let bytes = sparsevec_bytes(4, 2, &[0, 3], &[1.5, -1.0]);
+        assert_eq!(parse_sparsevec(&bytes).unwrap(), vec![1.5, 0.0, 0.0, -1.0]);
+
+        let empty = sparsevec_bytes(3, 0, &[], &[]);
+        assert_eq!(parse_sparsevec(&empty).unwrap(), vec![0.0; 3]);
+    }
+
+    #[test]
+    fn test_parse_invalid_sparsevec() {
+        for bytes in [
+            sparsevec_bytes(-1, 0, &[], &[]),
+            sparsevec_bytes(2, -1, &[], &[]),
+            sparsevec_bytes(1, 2, &[0, 1], &[1.0, 2.0]),
+            sparsevec_bytes(2, 1, &[2], &[1.0]),
+            sparsevec_bytes(2, 1, &[-1], &[1.0]),
+        ] {
+            assert!(matches!(
+                parse_sparsevec(&bytes),
+                Err(PgToPlError::DecodeError(_))
+            ));
+        }
+
+        // nnz annoncé sans les données correspondantes : rien n'est alloué
+        let truncated = sparsevec_bytes(i32::MAX, i32::MAX, &[], &[]);
+        assert!(matches!(
+            parse_sparsevec(&truncated),
+            Err(PgToPlError::NotEnoughBytes)
+        ));
+    }
+}
@@ -0,0 +1,185 @@
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use crate::{PgToPlError, utils::error::PgToPlResult};
+
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+/// Convertit l'EWKB de PostGIS en WKB ISO, renvoie aussi le SRID de la géométrie s'il est présent
+pub fn ewkb_to_wkb(bytes: &[u8]) -> PgToPlResult<(Vec<u8>, Option<i32>)> {
+    let mut reader = Reader { bytes, offset: 0 };
+    let mut wkb = Vec::with_capacity(bytes.len());
+    let srid = copy_geometry(&mut reader, &mut wkb)?;
+    Ok((wkb, srid))
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> PgToPlResult<&'a [u8]> {
+        let end = self.offset + len;
+        if end > self.bytes.len() {
+            return Err(PgToPlError::NotEnoughBytes);
+        }
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self, little_endian: bool) -> PgToPlResult<u32> {
+        let bytes = self.take(4)?;
+        Ok(if little_endian {
+            LittleEndian::read_u32(bytes)
+        } else {
+            BigEndian::read_u32(bytes)
+        })
+    }
+}
+
+fn copy_geometry(reader: &mut Reader, wkb: &mut Vec<u8>) -> PgToPlResult<Option<i32>> {
+    let byte_order = reader.take(1)?[0];
+    let little_endian = byte_order == 1;
+    let ewkb_type = reader.u32(little_endian)?;
+    let srid = match ewkb_type & EWKB_SRID {
+        0 => None,
+        _ => Some(reader.u32(little_endian)? as i32),
+    };
+
+    let base_type = ewkb_type & 0x0fff_ffff;
+    let has_z = ewkb_type & EWKB_Z != 0;
+    let has_m = ewkb_type & EWKB_M != 0;
+    let iso_type = base_type + if has_z { 1000 } else { 0 } + if has_m { 2000 } else { 0 };
+
+    wkb.push(byte_order);
+    let mut type_bytes = [0u8; 4];
+    if little_endian {
+        LittleEndian::write_u32(&mut type_bytes, iso_type);
+    } else {
+        BigEndian::write_u32(&mut type_bytes, iso_type);
+    }
+    wkb.extend_from_slice(&type_bytes);
+
+    let coord_len = 8 * (2 + has_z as usize + has_m as usize);
+    match base_type {
+        // point
+        1 => wkb.extend_from_slice(reader.take(coord_len)?),
+        // linestring, circularstring
+        2 | 8 => copy_points(reader, wkb, little_endian, coord_len)?,
+        // polygon, triangle : anneaux de points
+        3 | 17 => {
+            let rings = copy_count(reader, wkb, little_endian)?;
+            for _ in 0..rings {
+                copy_points(reader, wkb, little_endian, coord_len)?;
+            }
+        }
+        // multi*, collection, compoundcurve, curvepolygon, multicurve, multisurface,
+        // polyhedralsurface, tin : géométries imbriquées
+        4..=7 | 9..=12 | 15 | 16 => {
+            let count = copy_count(reader, wkb, little_endian)?;
+            for _ in 0..count {
+                copy_geometry(reader, wkb)?;
+            }
+        }
+        other => {
+            return Err(PgToPlError::DecodeError(
+                format!("Unsupported EWKB geometry type {}", other).into(),
+            ));
+        }
+    }
+
+    Ok(srid)
+}
+
+fn copy_count(reader: &mut Reader, wkb: &mut Vec<u8>, little_endian: bool) -> PgToPlResult<usize> {
+    let count = reader.u32(little_endian)?;
+    wkb.extend_from_slice(&reader.bytes[reader.offset - 4..reader.offset]);
+    Ok(count as usize)
+}
+
+fn copy_points(
+    reader: &mut Reader,
+    wkb: &mut Vec<u8>,
+    little_endian: bool,
+    coord_len: usize,
+) -> PgToPlResult<()> {
+    let points = copy_count(reader, wkb, little_endian)?;
+    wkb.extend_from_slice(reader.take(points * coord_len)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_with_srid() {
+        // POINT(1 2), SRID 4326, little endian
+        let mut ewkb = vec![1];
+        ewkb.extend_from_slice(&(1 | EWKB_SRID).to_le_bytes());
+        ewkb.extend_from_slice(&4326u32.to_le_bytes());
+        ewkb.extend_from_slice(&1f64.to_le_bytes());
+        ewkb.extend_from_slice(&2f64.to_le_bytes());
+
+        let (wkb, srid) = ewkb_to_wkb(&ewkb).unwrap();
+        assert_eq!(srid, Some(4326));
+        assert_eq!(wkb[..5], [1, 1, 0, 0, 0]);
+        assert_eq!(wkb[5..], ewkb[9..]);
+    }
+
+    #[test]
+    fn test_linestring_z_big_endian() {
+        // LINESTRING Z (0 0 0, 1 1 1), sans SRID : type ISO 1002
+        let mut ewkb = vec![0];
+        ewkb.extend_from_slice(&(2 | EWKB_Z).to_be_bytes());
+        ewkb.extend_from_slice(&2u32.to_be_bytes());
+        for coord in [0f64, 0.0, 0.0, 1.0, 1.0, 1.0] {
+            ewkb.extend_from_slice(&coord.to_be_bytes());
+        }
+
+        let (wkb, srid) = ewkb_to_wkb(&ewkb).unwrap();
+        assert_eq!(srid, None);
+        assert_eq!(wkb[..5], [0, 0, 0, 0x03, 0xea]);
+        assert_eq!(wkb[5..], ewkb[5..]);
+    }
+
+    #[test]
+    fn test_multipoint_m() {
+        // MULTIPOINT M ((1 2 3)) : type ISO 2004, point imbriqué 2001
+        let mut ewkb = vec![1];
+        ewkb.extend_from_slice(&(4 | EWKB_M).to_le_bytes());
+        ewkb.extend_from_slice(&1u32.to_le_bytes());
+        ewkb.push(1);
+        ewkb.extend_from_slice(&(1 | EWKB_M).to_le_bytes());
+        for coord in [1f64, 2.0, 3.0] {
+            ewkb.extend_from_slice(&coord.to_le_bytes());
+        }
+
+        let (wkb, _) = ewkb_to_wkb(&ewkb).unwrap();
+        assert_eq!(wkb[1..5], 2004u32.to_le_bytes());
+        assert_eq!(wkb[10..14], 2001u32.to_le_bytes());
+        assert_eq!(wkb.len(), ewkb.len());
+    }
+
+    #[test]
+    fn test_invalid_ewkb() {
+        // points annoncés mais absents
+        let mut ewkb = vec![1];
+        ewkb.extend_from_slice(&2u32.to_le_bytes());
+        ewkb.extend_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            ewkb_to_wkb(&ewkb),
+            Err(PgToPlError::NotEnoughBytes)
+        ));
+
+        let mut unknown = vec![1];
+        unknown.extend_from_slice(&99u32.to_le_bytes());
+        assert!(matches!(
+            ewkb_to_wkb(&unknown),
+            Err(PgToPlError::DecodeError(_))
+        ));
+    }
+}
@@ -19,7 +19,12 @@ fn create_test_client_option() -> ClientOptions {
 mod tests {
     use std::sync::Arc;
 
-    use postgres_to_polars::{BinaryParam, Client, PoolOptions, build_pool};
+    use polars::prelude::*;
+
+    use postgres_to_polars::{
+        BinaryParam, Client, NamedParams, PgToPlError, PgToPlResult, PoolOptions, ToParam,
+        build_pool,
+    };
     use tokio::task::JoinSet;
 
     use crate::create_test_client_option;
@@ -229,4 +234,445 @@ mod tests {
             result.expect("Task panicked");
         }
     }
+
+    #[tokio::test]
+    async fn test_rich_binary_params() {
+        let options = create_test_client_option();
+
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+
+        let params = vec![
+            Some(BinaryParam::Int2(7)),
+            Some(BinaryParam::Float4(1.5)),
+            Some(BinaryParam::Numeric {
+                mantissa: -1234567,
+                scale: 3,
+            }),
+            Some(BinaryParam::Numeric {
+                mantissa: 100000000,
+                scale: 0,
+            }),
+            Some(BinaryParam::Uuid([
+                0xa0, 0xee, 0xbc, 0x99, 0x9c, 0x0b, 0x4e, 0xf8, 0xbb, 0x6d, 0x6b, 0xb9, 0xbd, 0x38,
+                0x0a, 0x11,
+            ])),
+            Some(BinaryParam::Bytea(vec![0xde, 0xad])),
+            Some(BinaryParam::Date(19_724)), // 2024-01-02
+            Some(BinaryParam::Time(3_600_000_000)),
+            Some(BinaryParam::Timestamp(1_704_153_600_000_000)), // 2024-01-02 00:00:00
+            Some(BinaryParam::TimestampTz(1_704_153_600_000_000)),
+            Some(BinaryParam::Interval {
+                months: 1,
+                days: 2,
+                micros: 3_000_000,
+            }),
+            Some(BinaryParam::Json(String::from("{\"a\": 1}"))),
+            Some(BinaryParam::Jsonb(String::from("{\"a\": 1}"))),
+            Some(BinaryParam::Inet {
+                addr: "10.0.0.0".parse().unwrap(),
+                netmask: 8,
+            }),
+        ];
+
+        let df = client
+            .query(
+                "SELECT
+                    $1 = 7::int2
+                    AND $2 = 1.5::float4
+                    AND $3 = -1234.567
+                    AND $4 = 100000000
+                    AND $5 = 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'::uuid
+                    AND $6 = '\\xdead'::bytea
+                    AND $7 = '2024-01-02'::date
+                    AND $8 = '01:00:00'::time
+                    AND $9 = '2024-01-02 00:00:00'::timestamp
+                    AND $10 = '2024-01-02 00:00:00+00'::timestamptz
+                    AND $11 = interval '1 mon 2 days 00:00:03'
+                    AND $12::text = '{\"a\": 1}'
+                    AND $13 = '{\"a\": 1}'::jsonb
+                    AND $14 = '10.0.0.0/8'::inet AS all_equal",
+                params,
+            )
+            .await
+            .expect("Query failed");
+
+        let all_equal = df.column("all_equal").unwrap().bool().unwrap().get(0);
+        assert_eq!(all_equal, Some(true));
+    }
+
+    struct UserId(i32);
+
+    impl ToParam for UserId {
+        fn to_param(&self) -> PgToPlResult<Option<BinaryParam>> {
+            Ok(Some(BinaryParam::Int4(self.0)))
+        }
+
+        fn param_oid() -> u32 {
+            23
+        }
+    }
+
+    #[tokio::test]
+    async fn test_to_param_trait() {
+        let options = create_test_client_option();
+
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+
+        let df = client
+            .query_params(
+                "SELECT $1::int8 AS id, $2::text AS name, $3::float8 AS score, $4::bool AS missing",
+                (42i64, "alice", 1.5f64, None::<bool>),
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(df.column("id").unwrap().i64().unwrap().get(0), Some(42));
+        assert_eq!(
+            df.column("name").unwrap().str().unwrap().get(0),
+            Some("alice")
+        );
+        assert_eq!(df.column("missing").unwrap().null_count(), 1);
+
+        let name = String::from("bob");
+        let params: &[&dyn ToParam] = &[&UserId(7), &name];
+        let df = client
+            .query_params("SELECT $1::int4 AS id, $2::text AS name", params)
+            .await
+            .expect("Query failed");
+        assert_eq!(df.column("id").unwrap().i32().unwrap().get(0), Some(7));
+        assert_eq!(
+            df.column("name").unwrap().str().unwrap().get(0),
+            Some("bob")
+        );
+
+        let df = client
+            .query_params("SELECT $1::bytea AS raw", (vec![0xdeu8, 0xad],))
+            .await
+            .expect("Query failed");
+        assert_eq!(
+            df.column("raw").unwrap().binary().unwrap().get(0),
+            Some(&[0xdeu8, 0xad][..])
+        );
+
+        // entiers non signés comme les Series : u8 en smallint, u64 en numeric
+        let df = client
+            .query_params(
+                "SELECT $1::int2 AS small, $2::numeric::text AS big, $3::bytea AS raw",
+                (7u8, u64::MAX, &[1u8][..]),
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(df.column("small").unwrap().i16().unwrap().get(0), Some(7));
+        assert_eq!(
+            df.column("big").unwrap().str().unwrap().get(0),
+            Some("18446744073709551615")
+        );
+        assert_eq!(
+            df.column("raw").unwrap().binary().unwrap().get(0),
+            Some(&[1u8][..])
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[tokio::test]
+    async fn test_chrono_params() {
+        let options = create_test_client_option();
+
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let timestamp = date.and_hms_opt(3, 4, 5).unwrap();
+        let df = client
+            .query_params(
+                "SELECT $1 = '2024-01-02'::date AND $2 = '2024-01-02 03:04:05'::timestamp
+                    AND $3 = '2024-01-02 03:04:05+00'::timestamptz AS all_equal",
+                (date, timestamp, timestamp.and_utc()),
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(
+            df.column("all_equal").unwrap().bool().unwrap().get(0),
+            Some(true)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_array_params() {
+        let options = create_test_client_option();
+
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+
+        let df = client
+            .query_params(
+                "SELECT id FROM generate_series(1, 10) AS id WHERE id = ANY($1) ORDER BY id",
+                (vec![2i32, 5, 42],),
+            )
+            .await
+            .expect("Query failed");
+        let ids: Vec<Option<i32>> = df
+            .column("id")
+            .unwrap()
+            .i32()
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(ids, vec![Some(2), Some(5)]);
+
+        // type des éléments transmis par référence, ou lu sur la première valeur
+        let query = "SELECT id FROM generate_series(1, 10) AS id WHERE id = ANY($1) ORDER BY id";
+        let (two, five) = (2i32, 5i32);
+        let df = client
+            .query_params(query, (vec![&two, &five],))
+            .await
+            .expect("Query failed");
+        assert_eq!(df.height(), 2);
+        let df = client
+            .query_params(
+                query,
+                (vec![
+                    None,
+                    Some(BinaryParam::Int4(2)),
+                    Some(BinaryParam::Int4(5)),
+                ],),
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(df.height(), 2);
+
+        // `query` accepte toujours un itérateur de `BinaryParam`
+        let ids = [3, 4];
+        let df = client
+            .query(
+                "SELECT id FROM generate_series(1, 10) AS id WHERE id IN ($1, $2) ORDER BY id",
+                ids.iter().map(|id| Some(BinaryParam::Int4(*id))),
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(df.height(), 2);
+
+        // ids d'un premier DataFrame utilisés pour filtrer une seconde requête
+        let wanted = df! { "id" => [5i64, 7, 9] }.unwrap();
+        let df = client
+            .query_params(
+                "SELECT id FROM generate_series(1, 10) AS id WHERE id = ANY($1::int8[]) ORDER BY id",
+                (wanted.column("id").unwrap(),),
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(df.height(), 3);
+
+        let names = Series::new("names".into(), [Some("a"), None]);
+        let empty: Vec<String> = Vec::new();
+        let df = client
+            .query_params(
+                "SELECT cardinality($1::text[]) AS names, cardinality($2::text[]) AS empty",
+                (names, empty),
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(df.column("names").unwrap().i32().unwrap().get(0), Some(2));
+        assert_eq!(df.column("empty").unwrap().i32().unwrap().get(0), Some(0));
+
+        // UInt64 au-delà de i64::MAX envoyé en numeric, sans perte
+        let big = Series::new("big".into(), [u64::MAX, 5]);
+        let df = client
+            .query_params("SELECT ($1::numeric[])[1]::text AS big", (big,))
+            .await
+            .expect("Query failed");
+        assert_eq!(
+            df.column("big").unwrap().str().unwrap().get(0),
+            Some("18446744073709551615")
+        );
+
+        // une struct part en jsonb
+        let fields = [
+            Series::new("a".into(), [1i32]),
+            Series::new("b".into(), ["x\"y"]),
+        ];
+        let structs = StructChunked::from_series("s".into(), 1, fields.iter()).unwrap();
+        let df = client
+            .query_params(
+                "SELECT ($1::jsonb[])[1] ->> 'b' AS b",
+                (structs.into_series(),),
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(df.column("b").unwrap().str().unwrap().get(0), Some("x\"y"));
+    }
+
+    #[tokio::test]
+    async fn test_server_inferred_param_types() {
+        let options = create_test_client_option().with_infer_param_types(true);
+
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+
+        let query =
+            "SELECT id FROM generate_series(1::int8, 10::int8) AS id WHERE id = $1 OR $1 IS NULL";
+
+        // un NULL puis un i32 sur le même statement préparé
+        let df = client
+            .query_params(query, (None::<i32>,))
+            .await
+            .expect("Query failed");
+        assert_eq!(df.height(), 10);
+        let df = client
+            .query_params(query, (3i32,))
+            .await
+            .expect("Query failed");
+        assert_eq!(df.column("id").unwrap().i64().unwrap().get(0), Some(3));
+
+        let df = client
+            .query_params(
+                "SELECT $1::float8 AS ratio, $2::jsonb AS doc, $3::int2[] AS codes",
+                (1i32, "{\"a\": 1}", vec![1i64, 2]),
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(df.column("ratio").unwrap().f64().unwrap().get(0), Some(1.0));
+        assert_eq!(
+            df.column("doc").unwrap().str().unwrap().get(0),
+            Some("{\"a\": 1}")
+        );
+
+        // chaînes vers des types sans encodage binaire commun avec text
+        let df = client
+            .query_params(
+                "SELECT upper($1::int4range) AS upper, host($2::inet) AS host",
+                ("[1,5)", "10.0.0.1"),
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(df.column("upper").unwrap().i32().unwrap().get(0), Some(5));
+        assert_eq!(
+            df.column("host").unwrap().str().unwrap().get(0),
+            Some("10.0.0.1")
+        );
+
+        let result = client
+            .query_params("SELECT $1::int2 AS code", (100_000i32,))
+            .await;
+        assert!(result.is_err(), "Out of range value should be rejected");
+
+        // conversions sans perte seulement ; flottants vers numeric par leur écriture décimale
+        let df = client
+            .query_params(
+                "SELECT $1::real AS half, $2::numeric::text AS ratio, $3::float8 AS big",
+                (0.5f64, 1.25f64, 1i64 << 53),
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(df.column("half").unwrap().f32().unwrap().get(0), Some(0.5));
+        assert_eq!(
+            df.column("ratio").unwrap().str().unwrap().get(0),
+            Some("1.25")
+        );
+        for result in [
+            client.query_params("SELECT $1::real AS v", (0.1f64,)).await,
+            client
+                .query_params("SELECT $1::float8 AS v", ((1i64 << 53) + 1,))
+                .await,
+            client
+                .query_params("SELECT $1::numeric AS v", (f64::NAN,))
+                .await,
+        ] {
+            assert!(matches!(result, Err(PgToPlError::ParamCoercion(_, _))));
+        }
+
+        let result = client
+            .query_params("SELECT $1::int4 AS a, $2::int4 AS b", (1i32,))
+            .await;
+        assert!(result.is_err(), "Missing parameter should be rejected");
+    }
+
+    struct UserFilter {
+        min_id: i64,
+        label: &'static str,
+    }
+
+    impl NamedParams for UserFilter {
+        fn named_param(&self, name: &str) -> Option<&dyn ToParam> {
+            match name {
+                "min_id" => Some(&self.min_id),
+                "label" => Some(&self.label),
+                _ => None,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_named_params() {
+        let options = create_test_client_option();
+
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+
+        // nom répété, cast `::`, littéral, commentaires et corps dollar-quoté intacts
+        let query = "SELECT id, :label::text AS label, ':label' AS literal, \
+                     $body$ :label $x $body$ AS body -- :ignored
+                     FROM generate_series(1::int8, 10::int8) AS id /* :ignored */
+                     WHERE id >= :min_id AND id < $min_id + 3";
+
+        let params: std::collections::HashMap<&str, &dyn ToParam> =
+            [("min_id", &4i64 as &dyn ToParam), ("label", &"named")]
+                .into_iter()
+                .collect();
+        let df = client
+            .query_named(query, &params)
+            .await
+            .expect("Query failed");
+        assert_eq!(df.height(), 3);
+        assert_eq!(df.column("id").unwrap().i64().unwrap().get(0), Some(4));
+        assert_eq!(
+            df.column("label").unwrap().str().unwrap().get(0),
+            Some("named")
+        );
+        assert_eq!(
+            df.column("literal").unwrap().str().unwrap().get(0),
+            Some(":label")
+        );
+        assert_eq!(
+            df.column("body").unwrap().str().unwrap().get(0),
+            Some(" :label $x ")
+        );
+
+        let filter = UserFilter {
+            min_id: 8,
+            label: "struct",
+        };
+        let df = client
+            .query_named(query, &filter)
+            .await
+            .expect("Query failed");
+        assert_eq!(df.height(), 3);
+        assert_eq!(
+            df.column("label").unwrap().str().unwrap().get(0),
+            Some("struct")
+        );
+
+        let slice: [(&str, &dyn ToParam); 1] = [("min_id", &1i64)];
+        let result = client.query_named(query, &slice).await;
+        assert!(matches!(
+            result,
+            Err(PgToPlError::MissingNamedParam(name)) if name == "label"
+        ));
+
+        let df = client
+            .query_named(
+                "SELECT (ARRAY[10, 20, 30, 40])[2:3] AS part, ARRAY[:min_id] AS ids",
+                &slice,
+            )
+            .await
+            .expect("Query failed");
+        let part = df.column("part").unwrap().list().unwrap().get_as_series(0);
+        assert_eq!(part.unwrap().len(), 2);
+
+        let result = client
+            .query_named("SELECT $1::int4 AS a, :min_id AS b", &slice)
+            .await;
+        assert!(matches!(result, Err(PgToPlError::MixedParams)));
+    }
 }
@@ -0,0 +1,1155 @@
+use postgres_to_polars::ClientOptions;
+
+const USERNAME: &str = "POSTGRES_USER";
+const PASSWORD: &str = "pgpassword";
+const DATABASE: &str = "pg-database";
+
+fn create_test_client_option() -> ClientOptions {
+    ClientOptions::new(
+        String::from(USERNAME),
+        String::from(PASSWORD),
+        String::from(DATABASE),
+        String::from("127.0.0.1"),
+        5432,
+        true,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::*;
+    use postgres_to_polars::{
+        ArrayMode, BitMode, Client, EnumMode, GeometryMode, InetMode, MoneyMode, PgToPlError,
+        PgToPlResult, TimeTzMode, TypeDecoder, ValueBuilder,
+    };
+
+    use crate::create_test_client_option;
+
+    async fn connected_client() -> Client {
+        let client = Client::new(create_test_client_option()).await;
+        client.connect().await.expect("Failed to connect");
+        client
+    }
+
+    #[tokio::test]
+    async fn test_array_types() {
+        let client = connected_client().await;
+
+        let df = client
+            .query(
+                "SELECT
+                    ARRAY[1, NULL, 3]::int4[] AS ints,
+                    ARRAY[10000000000]::int8[] AS bigints,
+                    ARRAY[1.5, 2.5]::float8[] AS doubles,
+                    ARRAY[true, false]::bool[] AS bools,
+                    ARRAY['a', 'b']::varchar[] AS varchars,
+                    ARRAY['2024-01-02']::date[] AS dates,
+                    ARRAY[12.345, 1]::numeric[] AS numerics,
+                    ARRAY['a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11']::uuid[] AS uuids,
+                    ARRAY['{\"a\": 1}']::jsonb[] AS jsons,
+                    ARRAY[]::int4[] AS empty,
+                    NULL::int8[] AS nothing",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        assert_eq!(
+            df.column("ints").unwrap().dtype(),
+            &DataType::List(Box::new(DataType::Int32))
+        );
+        assert_eq!(
+            df.column("dates").unwrap().dtype(),
+            &DataType::List(Box::new(DataType::Date))
+        );
+        assert_eq!(
+            df.column("numerics").unwrap().dtype(),
+            &DataType::List(Box::new(DataType::Decimal(None, Some(3))))
+        );
+
+        let ints = df.column("ints").unwrap().list().unwrap().get_as_series(0);
+        let ints: Vec<Option<i32>> = ints.unwrap().i32().unwrap().into_iter().collect();
+        assert_eq!(ints, vec![Some(1), None, Some(3)]);
+
+        let uuids = df.column("uuids").unwrap().list().unwrap().get_as_series(0);
+        assert_eq!(
+            uuids.unwrap().str().unwrap().get(0),
+            Some("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11")
+        );
+
+        let jsons = df.column("jsons").unwrap().list().unwrap().get_as_series(0);
+        assert_eq!(jsons.unwrap().str().unwrap().get(0), Some("{\"a\": 1}"));
+
+        let empty = df.column("empty").unwrap().list().unwrap().get_as_series(0);
+        assert_eq!(empty.unwrap().len(), 0);
+        assert_eq!(df.column("nothing").unwrap().null_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_multi_dimension_arrays() {
+        let client = connected_client().await;
+
+        let df = client
+            .query(
+                "SELECT ARRAY[[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]::float8[][] AS matrix
+                UNION ALL SELECT NULL",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        let matrix = df.column("matrix").unwrap();
+        assert_eq!(
+            matrix.dtype(),
+            &DataType::List(Box::new(DataType::List(Box::new(DataType::Float64))))
+        );
+        let first = matrix.list().unwrap().get_as_series(0).unwrap();
+        assert_eq!(first.len(), 3);
+        let row = first.list().unwrap().get_as_series(2).unwrap();
+        let row: Vec<Option<f64>> = row.f64().unwrap().into_iter().collect();
+        assert_eq!(row, vec![Some(5.0), Some(6.0)]);
+        assert_eq!(matrix.null_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fixed_size_arrays_with_lower_bounds() {
+        let options = create_test_client_option()
+            .with_array_mode(ArrayMode::FixedWhenUniform)
+            .with_array_lower_bounds(true);
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+
+        let df = client
+            .query(
+                "SELECT '[0:1][1:2]={{1,2},{3,4}}'::int4[] AS matrix
+                UNION ALL SELECT '{{5,6},{7,8}}'::int4[]",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        assert_eq!(
+            df.column("matrix").unwrap().dtype(),
+            &DataType::Array(Box::new(DataType::Array(Box::new(DataType::Int32), 2)), 2)
+        );
+        let bounds = df
+            .column("matrix_lower_bounds")
+            .unwrap()
+            .list()
+            .unwrap()
+            .get_as_series(0)
+            .unwrap();
+        let bounds: Vec<Option<i32>> = bounds.i32().unwrap().into_iter().collect();
+        assert_eq!(bounds, vec![Some(0), Some(1)]);
+
+        let result = client
+            .query("SELECT ARRAY[1] AS a, 1 AS a_lower_bounds", vec![])
+            .await;
+        assert!(matches!(
+            result,
+            Err(PgToPlError::DuplicateColumn(name)) if name == "a_lower_bounds"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_mixed_dimension_arrays_error() {
+        let client = connected_client().await;
+
+        let result = client
+            .query(
+                "SELECT '{1,2}'::int4[] AS a UNION ALL SELECT '{{1},{2}}'::int4[]",
+                vec![],
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(PgToPlError::ArrayDimensionMismatch(1, 2))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_numeric_out_of_range() {
+        let client = connected_client().await;
+
+        let df = client
+            .query(
+                "SELECT v::numeric AS v FROM (VALUES ('1.5'), ('0.0001'), (NULL)) AS t(v)",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(
+            df.column("v").unwrap().dtype(),
+            &DataType::Decimal(None, Some(4))
+        );
+
+        // les deux valeurs tiennent seules, mais pas à l'échelle commune
+        let result = client
+            .query(
+                "SELECT v::numeric AS v
+                FROM (VALUES ('123456789012345678901234567890.5'), ('0.0000000001')) AS t(v)",
+                vec![],
+            )
+            .await;
+        assert!(matches!(result, Err(PgToPlError::NumericOverflow)));
+
+        // NaN et ±Infinity sans équivalent décimal : NULL, sans faire échouer la requête
+        let df = client
+            .query(
+                "SELECT v::numeric AS v
+                FROM (VALUES ('NaN'), ('Infinity'), ('-Infinity'), ('2.5')) AS t(v)",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+        let values = df.column("v").unwrap().decimal().unwrap();
+        assert_eq!(values.null_count(), 3);
+        assert_eq!(values.physical().get(3), Some(25));
+    }
+
+    #[tokio::test]
+    async fn test_enum_types() {
+        let client = connected_client().await;
+        client
+            .query(
+                "DO $$ BEGIN
+                    CREATE TYPE test_mood AS ENUM ('sad', 'ok', 'happy');
+                EXCEPTION WHEN duplicate_object THEN NULL;
+                END $$",
+                vec![],
+            )
+            .await
+            .expect("Failed to create enum");
+
+        let df = client
+            .query(
+                "SELECT 'happy'::test_mood AS mood, ARRAY['sad', 'ok']::test_mood[] AS moods",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        let mood_dtype = DataType::from_frozen_categories(
+            FrozenCategories::new(["sad", "ok", "happy"]).unwrap(),
+        );
+        let mood = df.column("mood").unwrap();
+        assert_eq!(mood.dtype(), &mood_dtype);
+        assert_eq!(
+            mood.cast(&DataType::String).unwrap().str().unwrap().get(0),
+            Some("happy")
+        );
+
+        let moods = df.column("moods").unwrap();
+        assert!(
+            matches!(moods.dtype(), DataType::List(inner) if matches!(**inner, DataType::Enum(_, _)))
+        );
+
+        let options = create_test_client_option().with_enum_mode(EnumMode::Categorical);
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+        let df = client
+            .query("SELECT 'ok'::test_mood AS mood", vec![])
+            .await
+            .expect("Query failed");
+        assert!(matches!(
+            df.column("mood").unwrap().dtype(),
+            DataType::Categorical(_, _)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_enum_label_added_after_caching() {
+        let client = connected_client().await;
+        client
+            .query("DROP TYPE IF EXISTS test_growing_mood", vec![])
+            .await
+            .expect("Failed to drop enum");
+        client
+            .query("CREATE TYPE test_growing_mood AS ENUM ('a', 'b')", vec![])
+            .await
+            .expect("Failed to create enum");
+
+        client
+            .query("SELECT 'a'::test_growing_mood AS mood", vec![])
+            .await
+            .expect("Query failed");
+        client
+            .query("ALTER TYPE test_growing_mood ADD VALUE 'c'", vec![])
+            .await
+            .expect("Failed to add enum value");
+
+        // les libellés en cache sont relus, la valeur n'est pas remplacée par NULL
+        let df = client
+            .query("SELECT 'c'::test_growing_mood AS mood", vec![])
+            .await
+            .expect("Query failed");
+        let mood = df.column("mood").unwrap();
+        assert_eq!(
+            mood.dtype(),
+            &DataType::from_frozen_categories(FrozenCategories::new(["a", "b", "c"]).unwrap())
+        );
+        assert_eq!(
+            mood.cast(&DataType::String).unwrap().str().unwrap().get(0),
+            Some("c")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_composite_types() {
+        let client = connected_client().await;
+        client
+            .query(
+                "DO $$ BEGIN
+                    CREATE TYPE test_address AS (street text, zip int4);
+                EXCEPTION WHEN duplicate_object THEN NULL;
+                END $$",
+                vec![],
+            )
+            .await
+            .expect("Failed to create composite type");
+
+        let df = client
+            .query(
+                "SELECT ROW('Main st', 75001)::test_address AS address, ROW(1, 'a'::text, NULL::float8) AS anonymous
+                UNION ALL SELECT NULL, NULL",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        let address = df.column("address").unwrap();
+        assert_eq!(
+            address.dtype(),
+            &DataType::Struct(vec![
+                Field::new("street".into(), DataType::String),
+                Field::new("zip".into(), DataType::Int32),
+            ])
+        );
+        assert_eq!(address.null_count(), 1);
+        let zip = address.struct_().unwrap().field_by_name("zip").unwrap();
+        assert_eq!(zip.i32().unwrap().get(0), Some(75001));
+
+        let anonymous = df.column("anonymous").unwrap();
+        assert_eq!(
+            anonymous.dtype(),
+            &DataType::Struct(vec![
+                Field::new("f1".into(), DataType::Int32),
+                Field::new("f2".into(), DataType::String),
+                Field::new("f3".into(), DataType::Float64),
+            ])
+        );
+        assert_eq!(anonymous.null_count(), 1);
+
+        // champs d'un record anonyme résolus dans le catalogue de la connexion
+        client
+            .query(
+                "DO $$ BEGIN
+                    CREATE TYPE test_record_mood AS ENUM ('low', 'high');
+                EXCEPTION WHEN duplicate_object THEN NULL;
+                END $$",
+                vec![],
+            )
+            .await
+            .expect("Failed to create enum");
+        let df = client
+            .query(
+                "SELECT NULL::record AS r
+                UNION ALL SELECT ROW('high'::test_record_mood, ROW('Side st', 1)::test_address, ROW(2))",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+        let record = df.column("r").unwrap().struct_().unwrap();
+        let fields = record.fields_as_series();
+        assert!(matches!(fields[0].dtype(), DataType::Enum(_, _)));
+        assert_eq!(
+            fields[0]
+                .cast(&DataType::String)
+                .unwrap()
+                .str()
+                .unwrap()
+                .get(1),
+            Some("high")
+        );
+        assert_eq!(
+            fields[1].dtype(),
+            &DataType::Struct(vec![
+                Field::new("street".into(), DataType::String),
+                Field::new("zip".into(), DataType::Int32),
+            ])
+        );
+        assert_eq!(
+            fields[2].dtype(),
+            &DataType::Struct(vec![Field::new("f1".into(), DataType::Int32)])
+        );
+        assert_eq!(df.column("r").unwrap().null_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_range_types() {
+        let client = connected_client().await;
+
+        let df = client
+            .query(
+                "SELECT
+                    int4range(1, 10) AS ints,
+                    numrange(NULL, 2.5, '(]') AS numerics,
+                    'empty'::daterange AS dates,
+                    '{[1,3), [5,8)}'::int4multirange AS multi
+                UNION ALL SELECT NULL, NULL, NULL, NULL",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        let ints = df.column("ints").unwrap();
+        assert_eq!(
+            ints.dtype(),
+            &DataType::Struct(vec![
+                Field::new("lower".into(), DataType::Int32),
+                Field::new("upper".into(), DataType::Int32),
+                Field::new("lower_inc".into(), DataType::Boolean),
+                Field::new("upper_inc".into(), DataType::Boolean),
+                Field::new("empty".into(), DataType::Boolean),
+            ])
+        );
+        assert_eq!(ints.null_count(), 1);
+        let ints = ints.struct_().unwrap();
+        assert_eq!(
+            ints.field_by_name("lower").unwrap().i32().unwrap().get(0),
+            Some(1)
+        );
+        assert_eq!(
+            ints.field_by_name("upper").unwrap().i32().unwrap().get(0),
+            Some(10)
+        );
+        assert_eq!(
+            ints.field_by_name("lower_inc")
+                .unwrap()
+                .bool()
+                .unwrap()
+                .get(0),
+            Some(true)
+        );
+        assert_eq!(
+            ints.field_by_name("upper_inc")
+                .unwrap()
+                .bool()
+                .unwrap()
+                .get(0),
+            Some(false)
+        );
+
+        let numerics = df.column("numerics").unwrap().struct_().unwrap();
+        assert_eq!(
+            numerics.field_by_name("lower").unwrap().get(0).unwrap(),
+            AnyValue::Null
+        );
+        assert_eq!(
+            numerics
+                .field_by_name("upper_inc")
+                .unwrap()
+                .bool()
+                .unwrap()
+                .get(0),
+            Some(true)
+        );
+
+        let dates = df.column("dates").unwrap().struct_().unwrap();
+        assert_eq!(
+            dates.field_by_name("lower").unwrap().dtype(),
+            &DataType::Date
+        );
+        assert_eq!(
+            dates.field_by_name("empty").unwrap().bool().unwrap().get(0),
+            Some(true)
+        );
+
+        let multi = df.column("multi").unwrap();
+        assert!(
+            matches!(multi.dtype(), DataType::List(inner) if matches!(**inner, DataType::Struct(_)))
+        );
+        let ranges = multi.list().unwrap().get_as_series(0).unwrap();
+        assert_eq!(ranges.len(), 2);
+        let uppers = ranges.struct_().unwrap().field_by_name("upper").unwrap();
+        let uppers: Vec<Option<i32>> = uppers.i32().unwrap().into_iter().collect();
+        assert_eq!(uppers, vec![Some(3), Some(8)]);
+        assert_eq!(multi.null_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_network_types() {
+        let client = connected_client().await;
+
+        let df = client
+            .query(
+                "SELECT
+                    '192.168.1.5'::inet AS host,
+                    '10.0.0.0/8'::cidr AS network,
+                    '2001:db8::1/64'::inet AS v6,
+                    '08:00:2b:01:02:03'::macaddr AS mac,
+                    '08:00:2b:01:02:03:04:05'::macaddr8 AS mac8,
+                    ARRAY['10.1.2.3/16']::inet[] AS hosts",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        let text = |name: &str| {
+            df.column(name)
+                .unwrap()
+                .str()
+                .unwrap()
+                .get(0)
+                .map(String::from)
+        };
+        assert_eq!(text("host").as_deref(), Some("192.168.1.5"));
+        assert_eq!(text("network").as_deref(), Some("10.0.0.0/8"));
+        assert_eq!(text("v6").as_deref(), Some("2001:db8::1/64"));
+        assert_eq!(text("mac").as_deref(), Some("08:00:2b:01:02:03"));
+        assert_eq!(text("mac8").as_deref(), Some("08:00:2b:01:02:03:04:05"));
+        let hosts = df.column("hosts").unwrap().list().unwrap().get_as_series(0);
+        assert_eq!(hosts.unwrap().str().unwrap().get(0), Some("10.1.2.3/16"));
+
+        let options = create_test_client_option().with_inet_mode(InetMode::Struct);
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+        let df = client
+            .query("SELECT '10.0.0.0/8'::cidr AS network", vec![])
+            .await
+            .expect("Query failed");
+
+        let network = df.column("network").unwrap().struct_().unwrap();
+        assert_eq!(
+            network
+                .field_by_name("family")
+                .unwrap()
+                .i16()
+                .unwrap()
+                .get(0),
+            Some(4)
+        );
+        assert_eq!(
+            network
+                .field_by_name("prefix")
+                .unwrap()
+                .i16()
+                .unwrap()
+                .get(0),
+            Some(8)
+        );
+        assert_eq!(
+            network
+                .field_by_name("addr")
+                .unwrap()
+                .binary()
+                .unwrap()
+                .get(0),
+            Some(&[10u8, 0, 0, 0][..])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_text_like_and_misc_scalar_types() {
+        let client = connected_client().await;
+        client
+            .query("CREATE EXTENSION IF NOT EXISTS citext", vec![])
+            .await
+            .expect("Failed to create citext extension");
+
+        let df = client
+            .query(
+                "SELECT
+                    'ab'::char(4) AS padded,
+                    'x'::\"char\" AS letter,
+                    (-23)::\"char\" AS high_letter,
+                    'pg_type'::name AS type_name,
+                    '<a>1</a>'::xml AS doc,
+                    'Hello'::citext AS insensitive,
+                    ARRAY['Hello', 'World']::citext[] AS insensitives,
+                    12.34::money AS amount,
+                    B'1011'::bit(4) AS flags,
+                    B'10'::varbit AS short_flags",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        let text = |name: &str| {
+            df.column(name)
+                .unwrap()
+                .str()
+                .unwrap()
+                .get(0)
+                .map(String::from)
+        };
+        assert_eq!(text("padded").as_deref(), Some("ab  "));
+        assert_eq!(text("letter").as_deref(), Some("x"));
+        assert_eq!(text("high_letter").as_deref(), Some("é")); // octet 0xE9 lu en Latin-1
+        assert_eq!(text("type_name").as_deref(), Some("pg_type"));
+        assert_eq!(text("doc").as_deref(), Some("<a>1</a>"));
+        assert_eq!(text("insensitive").as_deref(), Some("Hello"));
+        assert_eq!(text("flags").as_deref(), Some("1011"));
+        assert_eq!(text("short_flags").as_deref(), Some("10"));
+        assert_eq!(
+            df.column("insensitives").unwrap().dtype(),
+            &DataType::List(Box::new(DataType::String))
+        );
+        assert_eq!(
+            df.column("amount").unwrap().i64().unwrap().get(0),
+            Some(1234)
+        );
+        assert_eq!(client.money_scale().await, 2);
+
+        let options = create_test_client_option()
+            .with_trim_bpchar(true)
+            .with_money_mode(MoneyMode::Decimal)
+            .with_bit_mode(BitMode::List);
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+        let df = client
+            .query(
+                "SELECT 'ab'::char(4) AS padded, 12.34::money AS amount, B'101'::bit(3) AS flags",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        assert_eq!(
+            df.column("padded").unwrap().str().unwrap().get(0),
+            Some("ab")
+        );
+        assert_eq!(
+            df.column("amount").unwrap().dtype(),
+            &DataType::Decimal(None, Some(2))
+        );
+        let flags = df.column("flags").unwrap().list().unwrap().get_as_series(0);
+        let flags: Vec<Option<bool>> = flags.unwrap().bool().unwrap().into_iter().collect();
+        assert_eq!(flags, vec![Some(true), Some(false), Some(true)]);
+    }
+
+    #[tokio::test]
+    async fn test_timetz_types() {
+        let client = connected_client().await;
+
+        let df = client
+            .query(
+                "SELECT '10:30:00+02'::timetz AS meeting, ARRAY['23:00:00-05']::timetz[] AS meetings",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        let meeting = df.column("meeting").unwrap();
+        assert_eq!(
+            meeting.dtype(),
+            &DataType::Struct(vec![
+                Field::new("time".into(), DataType::Time),
+                Field::new("utc_offset_seconds".into(), DataType::Int32),
+            ])
+        );
+        let offset = meeting
+            .struct_()
+            .unwrap()
+            .field_by_name("utc_offset_seconds")
+            .unwrap();
+        assert_eq!(offset.i32().unwrap().get(0), Some(7200));
+
+        let options = create_test_client_option().with_timetz_mode(TimeTzMode::Utc);
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+        let df = client
+            .query(
+                "SELECT '10:30:00+02'::timetz AS meeting, '23:00:00-05'::timetz AS late",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        let nanos = |name: &str| {
+            let time = df.column(name).unwrap().cast(&DataType::Int64).unwrap();
+            time.i64().unwrap().get(0)
+        };
+        assert_eq!(
+            nanos("meeting"),
+            Some(8 * 3600 * 1_000_000_000 + 30 * 60 * 1_000_000_000)
+        );
+        assert_eq!(nanos("late"), Some(4 * 3600 * 1_000_000_000));
+    }
+
+    #[tokio::test]
+    async fn test_domain_types() {
+        let client = connected_client().await;
+        client
+            .query(
+                "DO $$ BEGIN
+                    CREATE DOMAIN test_email AS text CHECK (VALUE LIKE '%@%');
+                    CREATE DOMAIN test_positive_int AS int4 CHECK (VALUE > 0);
+                    CREATE DOMAIN test_small_positive AS test_positive_int CHECK (VALUE < 100);
+                    CREATE TYPE test_contact AS (email test_email, age test_positive_int);
+                EXCEPTION WHEN duplicate_object THEN NULL;
+                END $$",
+                vec![],
+            )
+            .await
+            .expect("Failed to create domains");
+
+        let df = client
+            .query(
+                "SELECT
+                    'a@b.c'::test_email AS email,
+                    42::test_positive_int AS age,
+                    7::test_small_positive AS small,
+                    ARRAY['x@y.z']::test_email[] AS emails,
+                    ROW('a@b.c', 30)::test_contact AS contact",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        assert_eq!(
+            df.column("email").unwrap().str().unwrap().get(0),
+            Some("a@b.c")
+        );
+        assert_eq!(df.column("age").unwrap().i32().unwrap().get(0), Some(42));
+        assert_eq!(df.column("small").unwrap().i32().unwrap().get(0), Some(7));
+        let emails = df
+            .column("emails")
+            .unwrap()
+            .list()
+            .unwrap()
+            .get_as_series(0);
+        assert_eq!(emails.unwrap().str().unwrap().get(0), Some("x@y.z"));
+        let age = df
+            .column("contact")
+            .unwrap()
+            .struct_()
+            .unwrap()
+            .field_by_name("age")
+            .unwrap();
+        assert_eq!(age.i32().unwrap().get(0), Some(30));
+    }
+
+    struct LowercaseDecoder;
+
+    impl TypeDecoder for LowercaseDecoder {
+        fn dtype(&self) -> DataType {
+            DataType::String
+        }
+
+        fn decode(&self, bytes: &[u8], builder: &mut ValueBuilder) -> PgToPlResult<()> {
+            let text = String::from_utf8_lossy(bytes).to_lowercase();
+            builder.push(AnyValue::StringOwned(text.into()));
+            Ok(())
+        }
+    }
+
+    struct WideIntDecoder;
+
+    impl TypeDecoder for WideIntDecoder {
+        fn dtype(&self) -> DataType {
+            DataType::Int64
+        }
+
+        fn decode(&self, bytes: &[u8], builder: &mut ValueBuilder) -> PgToPlResult<()> {
+            let bytes: [u8; 4] = bytes.try_into().map_err(|_| PgToPlError::NotEnoughBytes)?;
+            builder.push(AnyValue::Int64(i32::from_be_bytes(bytes) as i64));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_type_decoders() {
+        let options = create_test_client_option()
+            .with_decoder_for_type("citext", LowercaseDecoder)
+            .with_decoder_for_oid(23, WideIntDecoder);
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+        client
+            .query("CREATE EXTENSION IF NOT EXISTS citext", vec![])
+            .await
+            .expect("Failed to create citext extension");
+
+        let df = client
+            .query(
+                "SELECT 'HeLLo'::citext AS word, 42::int4 AS answer
+                UNION ALL SELECT NULL, NULL",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        let word = df.column("word").unwrap();
+        assert_eq!(word.str().unwrap().get(0), Some("hello"));
+        assert_eq!(word.null_count(), 1);
+        let answer = df.column("answer").unwrap();
+        assert_eq!(answer.dtype(), &DataType::Int64);
+        assert_eq!(answer.i64().unwrap().get(0), Some(42));
+    }
+
+    /// Crée l'extension si elle est installée sur le serveur de test, renvoie false sinon
+    async fn create_extension(client: &Client, extension: &str) -> bool {
+        let available = client
+            .query(
+                &format!(
+                    "SELECT name FROM pg_available_extensions WHERE name = '{}'",
+                    extension
+                ),
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+        if available.height() == 0 {
+            println!("Extension {} not available, skipping", extension);
+            return false;
+        }
+
+        client
+            .query(
+                &format!("CREATE EXTENSION IF NOT EXISTS {}", extension),
+                vec![],
+            )
+            .await
+            .expect("Failed to create extension");
+        true
+    }
+
+    #[tokio::test]
+    async fn test_pgvector_types() {
+        let client = connected_client().await;
+        if !create_extension(&client, "vector").await {
+            return;
+        }
+        // les OID de l'extension sont lus à la connexion
+        let client = connected_client().await;
+
+        let df = client
+            .query(
+                "SELECT
+                    '[1,2,3]'::vector(3) AS embedding,
+                    '[0.5,-1,2]'::halfvec AS half,
+                    '{1:1.5,3:2}/4'::sparsevec AS sparse
+                UNION ALL SELECT '[4,5,6]', NULL, NULL",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        let embedding = df.column("embedding").unwrap();
+        assert_eq!(
+            embedding.dtype(),
+            &DataType::Array(Box::new(DataType::Float32), 3)
+        );
+        let first = embedding.array().unwrap().get_as_series(1).unwrap();
+        let first: Vec<Option<f32>> = first.f32().unwrap().into_iter().collect();
+        assert_eq!(first, vec![Some(4.0), Some(5.0), Some(6.0)]);
+
+        let half = df.column("half").unwrap().array().unwrap().get_as_series(0);
+        let half: Vec<Option<f32>> = half.unwrap().f32().unwrap().into_iter().collect();
+        assert_eq!(half, vec![Some(0.5), Some(-1.0), Some(2.0)]);
+
+        let sparse = df
+            .column("sparse")
+            .unwrap()
+            .array()
+            .unwrap()
+            .get_as_series(0);
+        let sparse: Vec<Option<f32>> = sparse.unwrap().f32().unwrap().into_iter().collect();
+        assert_eq!(sparse, vec![Some(1.5), Some(0.0), Some(2.0), Some(0.0)]);
+    }
+
+    #[tokio::test]
+    async fn test_postgis_types() {
+        let client = connected_client().await;
+        if !create_extension(&client, "postgis").await {
+            return;
+        }
+        let query = "SELECT
+                ST_SetSRID(ST_MakePoint(1, 2), 4326) AS geom,
+                ST_MakePoint(1, 2, 3) AS point_z,
+                'POINT(1 2)'::geography AS geog,
+                ARRAY[ST_SetSRID(ST_MakePoint(1, 2), 3857)] AS geoms
+            UNION ALL SELECT NULL, NULL, NULL, NULL";
+
+        let client = connected_client().await;
+        let df = client.query(query, vec![]).await.expect("Query failed");
+
+        let mut point = vec![1u8, 1, 0, 0, 0];
+        point.extend(1f64.to_le_bytes());
+        point.extend(2f64.to_le_bytes());
+        let geom = df.column("geom").unwrap();
+        assert_eq!(geom.dtype(), &DataType::Binary);
+        assert_eq!(geom.binary().unwrap().get(0), Some(&point[..]));
+        assert_eq!(geom.null_count(), 1);
+        let srid = df.column("geom_srid").unwrap().i32().unwrap();
+        assert_eq!(srid.get(0), Some(4326));
+        assert_eq!(srid.get(1), None);
+        assert_eq!(
+            df.column("geog_srid").unwrap().i32().unwrap().get(0),
+            Some(4326)
+        );
+        // un SRID par colonne de premier niveau : geom, point_z, geog
+        assert_eq!(df.width(), 7);
+
+        // WKB ISO : type 1001 pour un point Z
+        let point_z = df
+            .column("point_z")
+            .unwrap()
+            .binary()
+            .unwrap()
+            .get(0)
+            .unwrap();
+        assert_eq!(&point_z[..5], &[1u8, 0xe9, 0x03, 0, 0]);
+
+        let options = create_test_client_option().with_geometry_mode(GeometryMode::Struct);
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+        let df = client.query(query, vec![]).await.expect("Query failed");
+
+        let geom = df.column("geom").unwrap();
+        assert_eq!(
+            geom.dtype(),
+            &DataType::Struct(vec![
+                Field::new("wkb".into(), DataType::Binary),
+                Field::new("srid".into(), DataType::Int32),
+            ])
+        );
+        assert_eq!(geom.null_count(), 1);
+        let srid = geom.struct_().unwrap().field_by_name("srid").unwrap();
+        assert_eq!(srid.i32().unwrap().get(0), Some(4326));
+        let geog = df.column("geog").unwrap().struct_().unwrap();
+        let geog_srid = geog.field_by_name("srid").unwrap();
+        assert_eq!(geog_srid.i32().unwrap().get(0), Some(4326));
+
+        // SRID conservé dans les tableaux
+        let geoms = df.column("geoms").unwrap().list().unwrap().get_as_series(0);
+        let nested_srid = geoms.unwrap().struct_().unwrap().field_by_name("srid");
+        assert_eq!(nested_srid.unwrap().i32().unwrap().get(0), Some(3857));
+
+        let options = create_test_client_option().with_geometry_mode(GeometryMode::Ewkb);
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+        let df = client.query(query, vec![]).await.expect("Query failed");
+
+        // EWKB : drapeau SRID et SRID avant les coordonnées
+        let ewkb = df.column("geom").unwrap().binary().unwrap().get(0).unwrap();
+        assert_eq!(&ewkb[..9], &[1u8, 1, 0, 0, 0x20, 0xe6, 0x10, 0, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_extension_type_names_outside_extension() {
+        let client = connected_client().await;
+        for statement in [
+            "CREATE SCHEMA IF NOT EXISTS test_not_pgvector",
+            "DROP TYPE IF EXISTS test_not_pgvector.vector",
+            "CREATE TYPE test_not_pgvector.vector AS (x int4)",
+        ] {
+            client.query(statement, vec![]).await.expect(statement);
+        }
+
+        // même nom que le type pgvector, mais hors de l'extension : décodé comme un composite
+        let client = connected_client().await;
+        let df = client
+            .query("SELECT ROW(1)::test_not_pgvector.vector AS v", vec![])
+            .await
+            .expect("Query failed");
+        assert_eq!(
+            df.column("v").unwrap().dtype(),
+            &DataType::Struct(vec![Field::new("x".into(), DataType::Int32)])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hstore_and_tsvector_types() {
+        let client = connected_client().await;
+        let with_hstore = create_extension(&client, "hstore").await;
+        let client = connected_client().await;
+
+        let df = client
+            .query(
+                "SELECT
+                    'fat:2A cat:3 rat:5B,7'::tsvector AS search,
+                    ''::tsvector AS empty
+                UNION ALL SELECT NULL, NULL",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        let search = df.column("search").unwrap();
+        assert_eq!(
+            search.dtype(),
+            &DataType::List(Box::new(DataType::Struct(vec![
+                Field::new("lexeme".into(), DataType::String),
+                Field::new(
+                    "positions".into(),
+                    DataType::List(Box::new(DataType::UInt16))
+                ),
+                Field::new("weights".into(), DataType::List(Box::new(DataType::String))),
+            ])))
+        );
+        assert_eq!(search.null_count(), 1);
+        let lexemes = search.list().unwrap().get_as_series(0).unwrap();
+        let lexemes = lexemes.struct_().unwrap();
+        let texts = lexemes.field_by_name("lexeme").unwrap();
+        let texts: Vec<Option<&str>> = texts.str().unwrap().into_iter().collect();
+        assert_eq!(texts, vec![Some("cat"), Some("fat"), Some("rat")]);
+        let rat_positions = lexemes
+            .field_by_name("positions")
+            .unwrap()
+            .list()
+            .unwrap()
+            .get_as_series(2)
+            .unwrap();
+        let rat_positions: Vec<Option<u16>> = rat_positions.u16().unwrap().into_iter().collect();
+        assert_eq!(rat_positions, vec![Some(5), Some(7)]);
+        let rat_weights = lexemes
+            .field_by_name("weights")
+            .unwrap()
+            .list()
+            .unwrap()
+            .get_as_series(2)
+            .unwrap();
+        let rat_weights: Vec<Option<&str>> = rat_weights.str().unwrap().into_iter().collect();
+        assert_eq!(rat_weights, vec![Some("B"), Some("D")]);
+        let empty = df.column("empty").unwrap().list().unwrap().get_as_series(0);
+        assert_eq!(empty.unwrap().len(), 0);
+
+        if !with_hstore {
+            return;
+        }
+        let df = client
+            .query(
+                "SELECT 'a=>1, b=>NULL'::hstore AS attributes UNION ALL SELECT NULL",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        let attributes = df.column("attributes").unwrap();
+        assert_eq!(attributes.null_count(), 1);
+        let pairs = attributes.list().unwrap().get_as_series(0).unwrap();
+        let pairs = pairs.struct_().unwrap();
+        let keys = pairs.field_by_name("key").unwrap();
+        let values = pairs.field_by_name("value").unwrap();
+        let mut entries: Vec<(Option<&str>, Option<&str>)> = keys
+            .str()
+            .unwrap()
+            .into_iter()
+            .zip(values.str().unwrap())
+            .collect();
+        entries.sort();
+        assert_eq!(entries, vec![(Some("a"), Some("1")), (Some("b"), None)]);
+    }
+
+    #[tokio::test]
+    async fn test_geometric_types() {
+        let client = connected_client().await;
+
+        let df = client
+            .query(
+                "SELECT
+                    point(1.5, 2) AS location,
+                    box(point(3, 4), point(0, 1)) AS area,
+                    circle(point(0, 0), 2) AS zone,
+                    '[(0,0),(1,1)]'::lseg AS segment,
+                    '((0,0),(4,0),(4,3))'::polygon AS room,
+                    '[(0,0),(1,2)]'::path AS route
+                UNION ALL SELECT NULL, NULL, NULL, NULL, NULL, NULL",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        let point_dtype = DataType::Struct(vec![
+            Field::new("x".into(), DataType::Float64),
+            Field::new("y".into(), DataType::Float64),
+        ]);
+        let location = df.column("location").unwrap();
+        assert_eq!(location.dtype(), &point_dtype);
+        assert_eq!(location.null_count(), 1);
+        let x = location.struct_().unwrap().field_by_name("x").unwrap();
+        assert_eq!(x.f64().unwrap().get(0), Some(1.5));
+
+        let area = df.column("area").unwrap();
+        assert_eq!(
+            area.dtype(),
+            &DataType::Struct(vec![
+                Field::new("high".into(), point_dtype.clone()),
+                Field::new("low".into(), point_dtype.clone()),
+            ])
+        );
+        let high = area.struct_().unwrap().field_by_name("high").unwrap();
+        let high_y = high.struct_().unwrap().field_by_name("y").unwrap();
+        assert_eq!(high_y.f64().unwrap().get(0), Some(4.0));
+
+        let zone = df.column("zone").unwrap().struct_().unwrap();
+        let radius = zone.field_by_name("radius").unwrap();
+        assert_eq!(radius.f64().unwrap().get(0), Some(2.0));
+
+        let segment = df.column("segment").unwrap().struct_().unwrap();
+        let end = segment.field_by_name("end").unwrap();
+        let end_x = end.struct_().unwrap().field_by_name("x").unwrap();
+        assert_eq!(end_x.f64().unwrap().get(0), Some(1.0));
+
+        let room = df.column("room").unwrap();
+        assert_eq!(room.dtype(), &DataType::List(Box::new(point_dtype)));
+        assert_eq!(room.null_count(), 1);
+        let corners = room.list().unwrap().get_as_series(0).unwrap();
+        let xs = corners.struct_().unwrap().field_by_name("x").unwrap();
+        let xs: Vec<Option<f64>> = xs.f64().unwrap().into_iter().collect();
+        assert_eq!(xs, vec![Some(0.0), Some(4.0), Some(4.0)]);
+
+        let route = df.column("route").unwrap();
+        assert_eq!(
+            route.dtype(),
+            &DataType::Struct(vec![
+                Field::new("closed".into(), DataType::Boolean),
+                Field::new("points".into(), room.dtype().clone()),
+            ])
+        );
+        assert_eq!(route.null_count(), 1);
+        let route = route.struct_().unwrap();
+        let closed = route.field_by_name("closed").unwrap();
+        assert_eq!(closed.bool().unwrap().get(0), Some(false));
+        let points = route.field_by_name("points").unwrap();
+        assert_eq!(points.list().unwrap().get_as_series(0).unwrap().len(), 2);
+
+        // chemin fermé : mêmes points, seul le drapeau change
+        let df = client
+            .query("SELECT '((0,0),(1,2))'::path AS loop", vec![])
+            .await
+            .expect("Query failed");
+        let closed = df.column("loop").unwrap().struct_().unwrap();
+        let closed = closed.field_by_name("closed").unwrap();
+        assert_eq!(closed.bool().unwrap().get(0), Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_text_fallback_for_unknown_types() {
+        let options = create_test_client_option().with_text_fallback(true);
+        let client = Client::new(options).await;
+        client.connect().await.expect("Failed to connect");
+
+        let df = client
+            .query(
+                "SELECT '16/B374D848'::pg_lsn AS lsn, 42::int4 AS answer,
+                    interval '1 day 02:00:00' AS duration
+                UNION ALL SELECT NULL, NULL, NULL",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+
+        let lsn = df.column("lsn").unwrap();
+        assert_eq!(lsn.dtype(), &DataType::String);
+        assert_eq!(lsn.str().unwrap().get(0), Some("16/B374D848"));
+        assert_eq!(lsn.null_count(), 1);
+        assert_eq!(df.column("answer").unwrap().i32().unwrap().get(0), Some(42));
+        assert_eq!(
+            df.column("duration").unwrap().str().unwrap().get(0),
+            Some("1 day 02:00:00")
+        );
+    }
+}
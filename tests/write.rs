@@ -0,0 +1,731 @@
+use postgres_to_polars::ClientOptions;
+
+const USERNAME: &str = "POSTGRES_USER";
+const PASSWORD: &str = "pgpassword";
+const DATABASE: &str = "pg-database";
+
+fn create_test_client_option() -> ClientOptions {
+    ClientOptions::new(
+        String::from(USERNAME),
+        String::from(PASSWORD),
+        String::from(DATABASE),
+        String::from("127.0.0.1"),
+        5432,
+        true,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::*;
+    use postgres_to_polars::{
+        Client, CopyInOptions, CreateTableOptions, PgToPlError, StructMode, TablePersistence,
+        UpsertResult, UpsertStrategy,
+    };
+
+    use crate::create_test_client_option;
+
+    async fn connected_client() -> Client {
+        let client = Client::new(create_test_client_option()).await;
+        client.connect().await.expect("Failed to connect");
+        client
+    }
+
+    #[tokio::test]
+    async fn test_copy_in() {
+        let client = connected_client().await;
+        client
+            .query(
+                "CREATE TEMP TABLE copy_in_target (
+                    id int8 PRIMARY KEY,
+                    label text,
+                    score float8,
+                    small int2,
+                    active bool,
+                    created_at timestamptz,
+                    tags int4[]
+                )",
+                vec![],
+            )
+            .await
+            .expect("Failed to create table");
+
+        let mut tags = ListChunked::from_iter([
+            Some(Series::new("".into(), [1i32, 2])),
+            None,
+            Some(Series::new("".into(), [3i32])),
+        ])
+        .into_series();
+        tags.rename("tags".into());
+        let created_at = Series::new("created_at".into(), [0i64, 1_000_000, 2_000_000])
+            .cast(&DataType::Datetime(
+                TimeUnit::Microseconds,
+                Some(TimeZone::UTC),
+            ))
+            .unwrap();
+        let df = DataFrame::new(vec![
+            Column::new("id".into(), [1i64, 2, 3]),
+            Column::new("name".into(), [Some("a"), None, Some("c")]),
+            Column::new("score".into(), [0.5f64, 1.5, 2.5]),
+            Column::new("small".into(), [1i64, 2, 3]),
+            Column::new("active".into(), [true, false, true]),
+            Column::new("ignored".into(), [0i32, 0, 0]),
+            created_at.into_column(),
+            tags.into_column(),
+        ])
+        .unwrap();
+
+        let options = CopyInOptions::new()
+            .with_columns([
+                "id",
+                "name",
+                "score",
+                "small",
+                "active",
+                "created_at",
+                "tags",
+            ])
+            .with_mapping("name", "label");
+        let count = client
+            .copy_in("copy_in_target", &df, options)
+            .await
+            .expect("Copy failed");
+        assert_eq!(count, 3);
+
+        let result = client
+            .query(
+                "SELECT id, label, score, small, active, extract(epoch FROM created_at)::int8 AS epoch, tags
+                FROM copy_in_target ORDER BY id",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(result.height(), 3);
+        let labels = result.column("label").unwrap().str().unwrap();
+        assert_eq!(labels.get(0), Some("a"));
+        assert_eq!(labels.get(1), None);
+        assert_eq!(
+            result.column("small").unwrap().i16().unwrap().get(2),
+            Some(3)
+        );
+        assert_eq!(
+            result.column("epoch").unwrap().i64().unwrap().get(2),
+            Some(2)
+        );
+        let tags = result.column("tags").unwrap().list().unwrap();
+        assert_eq!(tags.get_as_series(0).unwrap().len(), 2);
+        assert!(tags.get_as_series(1).is_none());
+
+        // un DataFrame vide ne copie rien
+        let count = client
+            .copy_in(
+                "copy_in_target",
+                &df.head(Some(0)),
+                CopyInOptions::new().with_columns(["id"]),
+            )
+            .await
+            .expect("Copy failed");
+        assert_eq!(count, 0);
+
+        // une valeur impossible à convertir annule la copie, le client reste utilisable
+        let bad = df! { "id" => ["not a number"] }.unwrap();
+        let result = client
+            .copy_in("copy_in_target", &bad, CopyInOptions::new())
+            .await;
+        assert!(matches!(result, Err(PgToPlError::QueryError(_))));
+        let result = client
+            .query("SELECT count(*) AS n FROM copy_in_target", vec![])
+            .await
+            .expect("Query failed");
+        assert_eq!(result.column("n").unwrap().i64().unwrap().get(0), Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_copy_in_round_trip() {
+        let client = connected_client().await;
+        client
+            .query(
+                "CREATE TEMP TABLE copy_round_trip (id uuid, ip inet, r int4range, code int2)",
+                vec![],
+            )
+            .await
+            .expect("Failed to create table");
+
+        // uuid et inet sont lus en chaînes, réécrits via leur entrée texte ; les ranges en struct
+        let mut df = client
+            .query(
+                "SELECT gen_random_uuid() AS id, '10.0.0.1'::inet AS ip,
+                    (ARRAY[int4range(1, 5), int4range(NULL, 3, '(]'), 'empty'])[n] AS r
+                FROM generate_series(1, 3) AS n",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+        df.with_column(Series::new("code".into(), [1u8, 2, 3]))
+            .unwrap();
+        let count = client
+            .copy_in("copy_round_trip", &df, CopyInOptions::new())
+            .await
+            .expect("Copy failed");
+        assert_eq!(count, 3);
+
+        let result = client
+            .query(
+                "SELECT id::text AS id, host(ip) AS host, r::text AS r, code
+                FROM copy_round_trip ORDER BY code",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(
+            result.column("id").unwrap().str().unwrap().get(0),
+            df.column("id").unwrap().str().unwrap().get(0)
+        );
+        assert_eq!(
+            result.column("host").unwrap().str().unwrap().get(0),
+            Some("10.0.0.1")
+        );
+        let ranges = result.column("r").unwrap().str().unwrap();
+        assert_eq!(ranges.get(0), Some("[1,5)"));
+        assert_eq!(ranges.get(1), Some("(,4)"));
+        assert_eq!(ranges.get(2), Some("empty"));
+        assert_eq!(
+            result.column("code").unwrap().i16().unwrap().get(1),
+            Some(2)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_copy_out_query() {
+        let client = connected_client().await;
+
+        let query = "SELECT id, 'row ' || id AS label, (id * 1.5)::numeric(10, 2) AS amount,
+                CASE WHEN id % 3 = 0 THEN NULL ELSE id::int4 END AS maybe,
+                ARRAY[id, id + 1] AS pair,
+                to_timestamp(id) AS created_at
+            FROM generate_series(1::int8, 2500::int8) AS id;";
+
+        let copied = client.copy_out_query(query).await.expect("Copy failed");
+        let queried = client.query(query, vec![]).await.expect("Query failed");
+        assert_eq!(copied.height(), 2500);
+        assert!(copied.equals_missing(&queried));
+
+        let empty = client
+            .copy_out_query("SELECT 1::int4 AS one WHERE false")
+            .await
+            .expect("Copy failed");
+        assert_eq!(empty.height(), 0);
+        assert_eq!(empty.column("one").unwrap().dtype(), &DataType::Int32);
+
+        let result = client.copy_out_query("SELECT * FROM missing_table").await;
+        assert!(matches!(result, Err(PgToPlError::QueryError(_))));
+
+        // une erreur de décodage garde son type et laisse la connexion utilisable
+        let result = client
+            .copy_out_query(
+                "SELECT v::numeric AS v
+                FROM (VALUES ('123456789012345678901234567890.5'), ('0.0000000001')) AS t(v)",
+            )
+            .await;
+        assert!(matches!(result, Err(PgToPlError::NumericOverflow)));
+        assert!(!client.has_broken());
+        let one = client
+            .copy_out_query("SELECT 1::int4 AS one")
+            .await
+            .expect("Copy failed");
+        assert_eq!(one.height(), 1);
+    }
+
+    async fn column_types(client: &Client, table: &str) -> Vec<(String, String)> {
+        let df = client
+            .query_params(
+                "SELECT attname::text AS name, format_type(atttypid, atttypmod) AS pg_type
+                FROM pg_attribute
+                WHERE attrelid = $1::text::regclass AND attnum > 0 AND NOT attisdropped
+                ORDER BY attnum",
+                (table,),
+            )
+            .await
+            .expect("Query failed");
+        let names = df.column("name").unwrap().str().unwrap();
+        let types = df.column("pg_type").unwrap().str().unwrap();
+        names
+            .into_iter()
+            .zip(types)
+            .map(|(name, pg_type)| (name.unwrap().to_string(), pg_type.unwrap().to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_create_table_from_schema() {
+        let client = connected_client().await;
+
+        let df = df! {
+            "Id" => [1i64, 2],
+            "name" => ["a", "b"],
+            "ratio" => [0.5f32, 1.5],
+            "flag" => [true, false],
+        }
+        .unwrap();
+        let mut schema = df.schema().as_ref().clone();
+        schema.with_column(
+            "created_at".into(),
+            DataType::Datetime(TimeUnit::Microseconds, Some(TimeZone::UTC)),
+        );
+        schema.with_column("day".into(), DataType::Date);
+        schema.with_column("amount".into(), DataType::Decimal(Some(12), Some(2)));
+        schema.with_column("tags".into(), DataType::List(Box::new(DataType::String)));
+        schema.with_column(
+            "grid".into(),
+            DataType::List(Box::new(DataType::List(Box::new(DataType::Int32)))),
+        );
+        schema.with_column(
+            "meta".into(),
+            DataType::Struct(vec![Field::new("zip".into(), DataType::Int32)]),
+        );
+        schema.with_column("rate".into(), DataType::Decimal(None, Some(4)));
+
+        let options = CreateTableOptions::new()
+            .with_persistence(TablePersistence::Temporary)
+            .with_primary_key(["Id"]);
+        client
+            .create_table_from_schema("Create Target", &schema, options.clone())
+            .await
+            .expect("Create failed");
+        assert_eq!(
+            column_types(&client, "\"Create Target\"").await,
+            [
+                ("Id", "bigint"),
+                ("name", "text"),
+                ("ratio", "real"),
+                ("flag", "boolean"),
+                ("created_at", "timestamp with time zone"),
+                ("day", "date"),
+                ("amount", "numeric(12,2)"),
+                ("tags", "text[]"),
+                ("grid", "integer[]"),
+                ("meta", "jsonb"),
+                ("rate", "numeric(38,4)"),
+            ]
+            .map(|(name, pg_type)| (name.to_string(), pg_type.to_string()))
+        );
+
+        // la table se remplit directement depuis le DataFrame d'origine
+        let count = client
+            .copy_in("\"Create Target\"", &df, CopyInOptions::new())
+            .await
+            .expect("Copy failed");
+        assert_eq!(count, 2);
+        let result = client
+            .copy_in("\"Create Target\"", &df, CopyInOptions::new())
+            .await;
+        assert!(result.is_err(), "Primary key should reject duplicates");
+
+        let result = client
+            .create_table_from_schema("Create Target", &schema, options.clone())
+            .await;
+        assert!(result.is_err());
+        client
+            .create_table_from_schema("Create Target", &schema, options.with_if_not_exists(true))
+            .await
+            .expect("IF NOT EXISTS should succeed");
+
+        // structure en type composite
+        let options = CreateTableOptions::new()
+            .with_persistence(TablePersistence::Temporary)
+            .with_struct_mode(StructMode::Composite)
+            .with_quote_identifiers(false)
+            .with_if_not_exists(true);
+        client
+            .create_table_from_schema("create_composite", &schema, options.clone())
+            .await
+            .expect("Create failed");
+        let types = column_types(&client, "create_composite").await;
+        assert_eq!(types[0], ("id".to_string(), "bigint".to_string()));
+        assert_eq!(
+            types[9],
+            ("meta".to_string(), "create_composite_meta".to_string())
+        );
+        // type existant réutilisé seulement s'il a les mêmes attributs
+        client
+            .create_table_from_schema("create_composite", &schema, options.clone())
+            .await
+            .expect("Same composite should be reused");
+        let mut changed = schema.clone();
+        changed.with_column(
+            "meta".into(),
+            DataType::Struct(vec![Field::new("zip".into(), DataType::String)]),
+        );
+        let result = client
+            .create_table_from_schema("create_composite", &changed, options.clone())
+            .await;
+        assert!(
+            matches!(&result, Err(err) if err.to_string().contains("different attributes")),
+            "{:?}",
+            result
+        );
+        // type temporaire comme la table
+        let result = client
+            .query(
+                "SELECT typnamespace = pg_my_temp_schema() AS temporary
+                FROM pg_type WHERE typname = 'create_composite_meta'",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(
+            result.column("temporary").unwrap().bool().unwrap().get(0),
+            Some(true)
+        );
+
+        let mut unsupported = Schema::default();
+        unsupported.with_column("duration".into(), DataType::Unknown(Default::default()));
+        let result = client
+            .create_table_from_schema(
+                "create_unsupported",
+                &unsupported,
+                CreateTableOptions::new(),
+            )
+            .await;
+        assert!(matches!(result, Err(PgToPlError::UnsupportedDataType(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_table_round_trip() {
+        let client = connected_client().await;
+
+        let fields = [
+            Series::new("zip".into(), [Some(75001i32), None]),
+            Series::new("city".into(), ["Paris", "l\"Haÿ"]),
+        ];
+        let meta = StructChunked::from_series("meta".into(), 2, fields.iter())
+            .unwrap()
+            .into_series();
+        let tags = Series::new(
+            "tags".into(),
+            [
+                Series::new("".into(), ["a", "b"]),
+                Series::new("".into(), ["c"]),
+            ],
+        );
+        let df = DataFrame::new(vec![
+            Column::new("id".into(), [1i64, 2]),
+            meta.into_column(),
+            Column::new_empty("nothing".into(), &DataType::Null)
+                .extend_constant(AnyValue::Null, 2)
+                .unwrap(),
+            tags.into_column(),
+        ])
+        .unwrap();
+
+        // même DataFrame pour le schéma et pour les données
+        client
+            .create_table_from_schema(
+                "create_round_trip",
+                df.schema(),
+                CreateTableOptions::new().with_persistence(TablePersistence::Temporary),
+            )
+            .await
+            .expect("Create failed");
+        let count = client
+            .copy_in("create_round_trip", &df, CopyInOptions::new())
+            .await
+            .expect("Copy failed");
+        assert_eq!(count, 2);
+
+        let rows = client
+            .query(
+                "SELECT id, meta::text AS meta, nothing, tags FROM create_round_trip ORDER BY id",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(
+            rows.column("meta")
+                .unwrap()
+                .str()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [
+                Some(r#"{"zip": 75001, "city": "Paris"}"#),
+                Some(r#"{"zip": null, "city": "l\"Haÿ"}"#),
+            ]
+        );
+        assert_eq!(rows.column("nothing").unwrap().null_count(), 2);
+        assert!(
+            rows.column("tags")
+                .unwrap()
+                .equals(df.column("tags").unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upsert_dataframe() {
+        let client = connected_client().await;
+
+        for strategy in [UpsertStrategy::OnConflict, UpsertStrategy::Merge] {
+            client
+                .query(
+                    "CREATE TEMP TABLE IF NOT EXISTS upsert_target (
+                        id int4 PRIMARY KEY,
+                        label text,
+                        score float8 DEFAULT 0
+                    )",
+                    vec![],
+                )
+                .await
+                .expect("Failed to create table");
+            client
+                .query("TRUNCATE upsert_target", vec![])
+                .await
+                .expect("Failed to truncate");
+
+            let df = df! { "id" => [1i64, 2], "label" => ["a", "b"] }.unwrap();
+            let result = client
+                .upsert_dataframe("upsert_target", &df, &["id"], strategy)
+                .await
+                .expect("Upsert failed");
+            assert_eq!(
+                result,
+                UpsertResult {
+                    inserted: 2,
+                    updated: 0
+                }
+            );
+
+            let df = df! { "id" => [2i64, 3], "label" => ["b2", "c"] }.unwrap();
+            let result = client
+                .upsert_dataframe("upsert_target", &df, &["id"], strategy)
+                .await
+                .expect("Upsert failed");
+            assert_eq!(
+                result,
+                UpsertResult {
+                    inserted: 1,
+                    updated: 1
+                }
+            );
+
+            let rows = client
+                .query("SELECT id, label FROM upsert_target ORDER BY id", vec![])
+                .await
+                .expect("Query failed");
+            let labels = rows.column("label").unwrap().str().unwrap();
+            assert_eq!(
+                labels.into_iter().collect::<Vec<_>>(),
+                [Some("a"), Some("b2"), Some("c")]
+            );
+
+            // une erreur annule tout, y compris les lignes valides
+            let df = df! { "id" => [4i64, 4], "label" => ["d", "d"] }.unwrap();
+            let result = client
+                .upsert_dataframe("upsert_target", &df, &["id"], strategy)
+                .await;
+            assert!(result.is_err(), "Duplicate keys should fail");
+            let rows = client
+                .query("SELECT count(*) AS n FROM upsert_target", vec![])
+                .await
+                .expect("Query failed");
+            assert_eq!(rows.column("n").unwrap().i64().unwrap().get(0), Some(3));
+        }
+
+        let df = df! { "id" => [5i64], "label" => ["e"] }.unwrap();
+        let result = client
+            .upsert_dataframe("upsert_target", &df, &[], UpsertStrategy::Merge)
+            .await;
+        assert!(matches!(result, Err(PgToPlError::MissingKeyColumns)));
+
+        // dans une transaction de l'appelant : savepoint, validé ou annulé par l'appelant
+        client.query("BEGIN", vec![]).await.expect("Begin failed");
+        for strategy in [UpsertStrategy::OnConflict, UpsertStrategy::Merge] {
+            client
+                .upsert_dataframe("upsert_target", &df, &["id"], strategy)
+                .await
+                .expect("Upsert failed");
+        }
+        let bad = df! { "id" => [6i64, 6], "label" => ["f", "f"] }.unwrap();
+        let result = client
+            .upsert_dataframe("upsert_target", &bad, &["id"], UpsertStrategy::OnConflict)
+            .await;
+        assert!(result.is_err(), "Duplicate keys should fail");
+        // une erreur du serveur ne condamne ni la transaction ni la connexion
+        let result = client
+            .upsert_dataframe("missing_table", &df, &["id"], UpsertStrategy::OnConflict)
+            .await;
+        assert!(matches!(result, Err(PgToPlError::QueryError(_))));
+        assert!(!client.has_broken());
+        let rows = client
+            .query("SELECT count(*) AS n FROM upsert_target", vec![])
+            .await
+            .expect("Query failed");
+        assert_eq!(rows.column("n").unwrap().i64().unwrap().get(0), Some(4));
+        client
+            .query("ROLLBACK", vec![])
+            .await
+            .expect("Rollback failed");
+        let rows = client
+            .query("SELECT count(*) AS n FROM upsert_target", vec![])
+            .await
+            .expect("Query failed");
+        assert_eq!(rows.column("n").unwrap().i64().unwrap().get(0), Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_insert_dataframe_unnest() {
+        let client = connected_client().await;
+        client
+            .query(
+                "CREATE TEMP TABLE unnest_target (
+                    id int4 PRIMARY KEY,
+                    label text,
+                    amount numeric(10, 2),
+                    day date,
+                    ref uuid
+                )",
+                vec![],
+            )
+            .await
+            .expect("Failed to create table");
+
+        let day = Series::new("day".into(), [19_000i32; 5])
+            .cast(&DataType::Date)
+            .unwrap();
+        let df = DataFrame::new(vec![
+            Column::new("id".into(), [1i64, 2, 3, 4, 5]),
+            Column::new(
+                "label".into(),
+                [Some("a"), None, Some("c"), Some("d"), Some("e")],
+            ),
+            Column::new("amount".into(), [1.5f64, 2.0, 2.5, 3.0, 3.5]),
+            day.into_column(),
+            // chaînes converties vers le type de la colonne cible
+            Column::new(
+                "ref".into(),
+                [
+                    Some("00000000-0000-0000-0000-000000000001"),
+                    None,
+                    None,
+                    None,
+                    None,
+                ],
+            ),
+        ])
+        .unwrap();
+        let count = client
+            .insert_dataframe_unnest("unnest_target", &df, 2)
+            .await
+            .expect("Insert failed");
+        assert_eq!(count, 5);
+
+        let rows = client
+            .query(
+                "SELECT id, label, amount::float8 AS amount, day, ref::text AS ref
+                FROM unnest_target ORDER BY id",
+                vec![],
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(rows.height(), 5);
+        assert_eq!(rows.column("label").unwrap().str().unwrap().get(1), None);
+        assert_eq!(
+            rows.column("amount").unwrap().f64().unwrap().get(4),
+            Some(3.5)
+        );
+        assert_eq!(
+            rows.column("day").unwrap().date().unwrap().phys.get(0),
+            Some(19_000)
+        );
+        assert_eq!(
+            rows.column("ref").unwrap().str().unwrap().get(0),
+            Some("00000000-0000-0000-0000-000000000001")
+        );
+
+        // le total vient des tags INSERT : les lignes écartées par un trigger ne comptent pas
+        client
+            .query(
+                "CREATE FUNCTION pg_temp.skip_negative() RETURNS trigger AS $$
+                BEGIN RETURN CASE WHEN NEW.id < 0 THEN NULL ELSE NEW END; END $$ LANGUAGE plpgsql",
+                vec![],
+            )
+            .await
+            .expect("Failed to create function");
+        client
+            .query(
+                "CREATE TRIGGER skip_negative BEFORE INSERT ON unnest_target
+                FOR EACH ROW EXECUTE FUNCTION pg_temp.skip_negative()",
+                vec![],
+            )
+            .await
+            .expect("Failed to create trigger");
+        let df = df! { "id" => [-1i64, 6, -2] }.unwrap();
+        let count = client
+            .insert_dataframe_unnest("unnest_target", &df, 2)
+            .await
+            .expect("Insert failed");
+        assert_eq!(count, 1);
+
+        // un lot en erreur annule les lots précédents
+        let df = df! { "id" => [10i64, 11, 12, 1] }.unwrap();
+        let result = client
+            .insert_dataframe_unnest("unnest_target", &df, 2)
+            .await;
+        assert!(result.is_err());
+        let rows = client
+            .query("SELECT count(*) AS n FROM unnest_target", vec![])
+            .await
+            .expect("Query failed");
+        assert_eq!(rows.column("n").unwrap().i64().unwrap().get(0), Some(6));
+
+        // dans une transaction de l'appelant, un lot en erreur n'annule que cet appel
+        client.query("BEGIN", vec![]).await.expect("Begin failed");
+        let df = df! { "id" => [20i64] }.unwrap();
+        let count = client
+            .insert_dataframe_unnest("unnest_target", &df, 2)
+            .await
+            .expect("Insert failed");
+        assert_eq!(count, 1);
+        let df = df! { "id" => [21i64, 20] }.unwrap();
+        let result = client
+            .insert_dataframe_unnest("unnest_target", &df, 2)
+            .await;
+        assert!(result.is_err());
+        let result = client
+            .insert_dataframe_unnest("missing_table", &df, 2)
+            .await;
+        assert!(matches!(result, Err(PgToPlError::QueryError(_))));
+        assert!(!client.has_broken());
+        let rows = client
+            .query("SELECT count(*) AS n FROM unnest_target", vec![])
+            .await
+            .expect("Query failed");
+        assert_eq!(rows.column("n").unwrap().i64().unwrap().get(0), Some(7));
+        client
+            .query("ROLLBACK", vec![])
+            .await
+            .expect("Rollback failed");
+        let rows = client
+            .query("SELECT count(*) AS n FROM unnest_target", vec![])
+            .await
+            .expect("Query failed");
+        assert_eq!(rows.column("n").unwrap().i64().unwrap().get(0), Some(6));
+
+        // colonnes tableaux refusées, List comme Array
+        let list = Series::new("id".into(), [Series::new("".into(), [1i32])]);
+        let array = list
+            .cast(&DataType::Array(Box::new(DataType::Int32), 1))
+            .unwrap();
+        for column in [list, array] {
+            let df = DataFrame::new(vec![column.into_column()]).unwrap();
+            let result = client
+                .insert_dataframe_unnest("unnest_target", &df, 2)
+                .await;
+            assert!(
+                matches!(result, Err(PgToPlError::UnsupportedDataType(_))),
+                "{:?}",
+                result
+            );
+        }
+        assert!(!client.has_broken());
+    }
+}